@@ -0,0 +1,371 @@
+//! `#[derive(ContractAccessors)]`, a procedural macro that writes get_* and set_* functions.
+//!
+//! Written for lesson_5_macro_usage, and used by lesson_3_structs.
+//!
+//! Each field marked with `#[accessor(...)]` gets functions on the struct itself:
+//!
+//!  - `get`: `pub fn get_field(&self) -> Type`, returning a clone of the field.
+//!  - `set`: `pub fn set_field(&mut self, field: Type)`.
+//!  - `arg = name`: the name of the setter argument, which is also the JSON argument name. Defaults to the field name.
+//!
+//! With `#[accessors(contract = Contract, field = state)]` on the struct, the same functions are also
+//! generated in a `#[near_bindgen] impl Contract`, forwarding to `self.state`. If that field is a wrapper,
+//! `read = method` and `write = method` name the functions that borrow the struct from it.
+//!
+//! ```ignore
+//! #[derive(ContractAccessors)]
+//! #[accessors(contract = Contract, field = struct_example, read = get, write = get_mut)]
+//! pub struct StructExample {
+//!     #[accessor(get, set)]
+//!     a_boolean: bool,
+//!     // no accessors, the contract writes its own
+//!     an_integer: u32,
+//! }
+//! ```
+//!
+//! Setters that need validation should be written by hand, marking the field with just `#[accessor(get)]`.
+//!
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{
+    parse_macro_input,
+    Attribute,
+    Data,
+    DeriveInput,
+    Error,
+    Fields,
+    Ident,
+    Type,
+};
+
+
+// Options from #[accessors(...)], describing the contract that forwards to the struct
+struct Forwarding {
+    contract: Ident,
+    field: Ident,
+    read: Option<Ident>,
+    write: Option<Ident>,
+}
+
+// Options from #[accessor(...)] for a single field
+struct Accessor {
+    name: Ident,
+    ty: Type,
+    docs: Vec<Attribute>,
+    get: bool,
+    set: bool,
+    arg: Ident,
+}
+
+
+#[proc_macro_derive(ContractAccessors, attributes(accessors, accessor))]
+pub fn derive_contract_accessors(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    expand(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let forwarding: Option<Forwarding> = parse_forwarding(&input)?;
+
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => return Err(Error::new_spanned(&input.ident, "ContractAccessors needs a struct with named fields")),
+        },
+        _ => return Err(Error::new_spanned(&input.ident, "ContractAccessors can only be derived for structs")),
+    };
+
+    let mut accessors: Vec<Accessor> = Vec::new();
+    for field in fields {
+        if let Some(accessor) = parse_accessor(field)? {
+            accessors.push(accessor);
+        }
+    }
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let inner = accessors.iter().map(inner_functions);
+
+    let mut tokens = quote! {
+        impl #impl_generics #name #ty_generics #where_clause {
+            #(#inner)*
+        }
+    };
+
+    if let Some(forwarding) = forwarding {
+        let contract = &forwarding.contract;
+        let forwarded = accessors.iter().map(|accessor| forwarded_functions(accessor, &forwarding));
+
+        tokens.extend(quote! {
+            #[::near_sdk::near_bindgen]
+            impl #contract {
+                #(#forwarded)*
+            }
+        });
+    }
+
+    Ok(tokens)
+}
+
+
+fn parse_forwarding(input: &DeriveInput) -> syn::Result<Option<Forwarding>> {
+    let mut contract: Option<Ident> = None;
+    let mut field: Option<Ident> = None;
+    let mut read: Option<Ident> = None;
+    let mut write: Option<Ident> = None;
+
+    let mut found: Option<&Attribute> = None;
+
+    for attr in input.attrs.iter().filter(|attr| attr.path().is_ident("accessors")) {
+        found = Some(attr);
+
+        attr.parse_nested_meta(|meta| {
+            let target: &mut Option<Ident> = if meta.path.is_ident("contract") {
+                &mut contract
+            } else if meta.path.is_ident("field") {
+                &mut field
+            } else if meta.path.is_ident("read") {
+                &mut read
+            } else if meta.path.is_ident("write") {
+                &mut write
+            } else {
+                return Err(meta.error("expected contract, field, read or write"));
+            };
+
+            *target = Some(meta.value()?.parse()?);
+
+            Ok(())
+        })?;
+    }
+
+    match (found, contract, field) {
+        (None, _, _) => Ok(None),
+        (_, Some(contract), Some(field)) => Ok(Some(Forwarding { contract, field, read, write })),
+        (Some(attr), _, _) => Err(Error::new_spanned(attr, "#[accessors] needs both contract = Name and field = name")),
+    }
+}
+
+
+fn parse_accessor(field: &syn::Field) -> syn::Result<Option<Accessor>> {
+    let attr: &Attribute = match field.attrs.iter().find(|attr| attr.path().is_ident("accessor")) {
+        Some(attr) => attr,
+        None => return Ok(None),
+    };
+
+    // Named fields always have an ident
+    let name: Ident = field.ident.clone().unwrap();
+
+    let mut accessor = Accessor {
+        arg: name.clone(),
+        name,
+        ty: field.ty.clone(),
+        docs: field.attrs.iter().filter(|attr| attr.path().is_ident("doc")).cloned().collect(),
+        get: false,
+        set: false,
+    };
+
+    attr.parse_nested_meta(|meta| {
+        if meta.path.is_ident("get") {
+            accessor.get = true;
+        } else if meta.path.is_ident("set") {
+            accessor.set = true;
+        } else if meta.path.is_ident("arg") {
+            accessor.arg = meta.value()?.parse()?;
+        } else {
+            return Err(meta.error("expected get, set or arg"));
+        }
+
+        Ok(())
+    })?;
+
+    if !accessor.get && !accessor.set {
+        return Err(Error::new_spanned(attr, "#[accessor] needs get, set or both"));
+    }
+
+    Ok(Some(accessor))
+}
+
+
+// get_* and set_* for the struct itself
+fn inner_functions(accessor: &Accessor) -> TokenStream2 {
+    let Accessor { name, ty, docs, arg, .. } = accessor;
+    let getter = format_ident!("get_{}", name);
+    let setter = format_ident!("set_{}", name);
+
+    let mut tokens = TokenStream2::new();
+
+    if accessor.get {
+        let doc = format!("Returns a copy of `{}`.", name);
+
+        tokens.extend(quote! {
+            #(#docs)*
+            #[doc = #doc]
+            pub fn #getter(&self) -> #ty {
+                ::std::clone::Clone::clone(&self.#name)
+            }
+        });
+    }
+
+    if accessor.set {
+        let doc = format!("Replaces `{}`.", name);
+
+        tokens.extend(quote! {
+            #[doc = #doc]
+            pub fn #setter(&mut self, #arg: #ty) {
+                self.#name = #arg;
+            }
+        });
+    }
+
+    tokens
+}
+
+
+// get_* and set_* for the contract, calling the functions above
+fn forwarded_functions(accessor: &Accessor, forwarding: &Forwarding) -> TokenStream2 {
+    let Accessor { name, ty, docs, arg, .. } = accessor;
+    let getter = format_ident!("get_{}", name);
+    let setter = format_ident!("set_{}", name);
+
+    let field = &forwarding.field;
+    let read = forwarding.read.as_ref().map(|method| quote!(.#method()));
+    let write = forwarding.write.as_ref().map(|method| quote!(.#method()));
+
+    let mut tokens = TokenStream2::new();
+
+    if accessor.get {
+        tokens.extend(quote! {
+            #(#docs)*
+            pub fn #getter(&self) -> #ty {
+                self.#field #read .#getter()
+            }
+        });
+    }
+
+    if accessor.set {
+        tokens.extend(quote! {
+            pub fn #setter(&mut self, #arg: #ty) {
+                self.#field #write .#setter(#arg)
+            }
+        });
+    }
+
+    tokens
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::parse_quote;
+
+    // Expands the derive and returns the code as a string, without spaces, so it's easier to search
+    fn expand_to_string(input: DeriveInput) -> String {
+        expand(input).unwrap().to_string().replace(' ', "")
+    }
+
+    fn expand_error(input: DeriveInput) -> String {
+        expand(input).unwrap_err().to_string()
+    }
+
+    #[test]
+    pub fn getters_and_setters() {
+        let code = expand_to_string(parse_quote! {
+            pub struct Example {
+                #[accessor(get)]
+                an_integer: u32,
+                #[accessor(get, set, arg = a_string_arg)]
+                a_string: String,
+                no_accessor: bool,
+            }
+        });
+
+        assert!(code.contains("pubfnget_an_integer(&self)->u32"));
+        assert!(!code.contains("set_an_integer"));
+        assert!(code.contains("pubfnset_a_string(&mutself,a_string_arg:String){self.a_string=a_string_arg;}"));
+        assert!(!code.contains("no_accessor"));
+
+        // Without #[accessors], nothing is written for a contract
+        assert!(!code.contains("near_bindgen"));
+    }
+
+    #[test]
+    pub fn forwarding() {
+        let code = expand_to_string(parse_quote! {
+            #[accessors(contract = Contract, field = state, read = get, write = get_mut)]
+            pub struct Example {
+                #[accessor(get, set)]
+                a_boolean: bool,
+            }
+        });
+
+        assert!(code.contains("#[::near_sdk::near_bindgen]implContract"));
+        assert!(code.contains("self.state.get().get_a_boolean()"));
+        assert!(code.contains("self.state.get_mut().set_a_boolean(a_boolean)"));
+    }
+
+    #[test]
+    pub fn forwarding_without_wrapper() {
+        let code = expand_to_string(parse_quote! {
+            #[accessors(contract = Contract, field = state)]
+            pub struct Example {
+                #[accessor(get)]
+                a_boolean: bool,
+            }
+        });
+
+        assert!(code.contains("self.state.get_a_boolean()"));
+    }
+
+    #[test]
+    pub fn errors() {
+        assert_eq!(
+            expand_error(parse_quote! {
+                #[accessors(contract = Contract)]
+                pub struct Example {}
+            }),
+            "#[accessors] needs both contract = Name and field = name",
+        );
+
+        assert_eq!(
+            expand_error(parse_quote! {
+                pub struct Example {
+                    #[accessor()]
+                    a_boolean: bool,
+                }
+            }),
+            "#[accessor] needs get, set or both",
+        );
+
+        assert_eq!(
+            expand_error(parse_quote! {
+                pub struct Example {
+                    #[accessor(get, remove)]
+                    a_boolean: bool,
+                }
+            }),
+            "expected get, set or arg",
+        );
+
+        assert_eq!(
+            expand_error(parse_quote! {
+                pub struct Example(bool);
+            }),
+            "ContractAccessors needs a struct with named fields",
+        );
+
+        assert_eq!(
+            expand_error(parse_quote! {
+                pub enum Example { A }
+            }),
+            "ContractAccessors can only be derived for structs",
+        );
+    }
+}
@@ -0,0 +1,54 @@
+use contract_accessors_derive::ContractAccessors;
+
+
+#[derive(ContractAccessors, Default)]
+pub struct Example {
+    #[accessor(get)]
+    an_integer: u32,
+    /// A string with a setter that has its own argument name
+    #[accessor(get, set, arg = a_string_arg)]
+    a_string: String,
+    #[accessor(set)]
+    a_boolean: bool,
+}
+
+impl Example {
+    // Setters that check the value are written by hand
+    pub fn set_an_integer(&mut self, an_integer: u32) {
+        assert!(an_integer <= 10, "an_integer must be at most 10");
+
+        self.an_integer = an_integer;
+    }
+
+    pub fn is_a_boolean(&self) -> bool {
+        self.a_boolean
+    }
+}
+
+
+#[test]
+pub fn getters_and_setters() {
+    let mut example = Example::default();
+
+    assert_eq!(example.get_an_integer(), 0);
+    assert_eq!(example.get_a_string(), "");
+
+    example.set_an_integer(5);
+    example.set_a_string(String::from("A string"));
+    example.set_a_boolean(true);
+
+    assert_eq!(example.get_an_integer(), 5);
+    assert_eq!(example.get_a_string(), "A string");
+    assert!(example.is_a_boolean());
+}
+
+#[test]
+pub fn getter_returns_a_copy() {
+    let mut example = Example::default();
+    example.set_a_string(String::from("First"));
+
+    let mut copy: String = example.get_a_string();
+    copy.push_str(" changed");
+
+    assert_eq!(example.get_a_string(), "First");
+}
@@ -0,0 +1,275 @@
+//! Leveled logging shared by every lesson.
+//!
+//! Lessons used to redefine the same `log` function, printing with `println!` during tests
+//! and with `env::log` in the virtual machine. This crate replaces those copies with a single macro:
+//!
+//! ```ignore
+//! use contract_log::contract_log;
+//!
+//! contract_log!(debug, "Called add_user.");
+//! contract_log!(info, "Successfully removed all entries for {}.", &account_id);
+//! ```
+//!
+//! Levels, from lowest to highest: `trace`, `debug`, `info`, `warn`, `error`.
+//!
+//!  - The minimum level is chosen at compile time with the features `min-level-trace`, `min-level-debug`,
+//!    `min-level-info`, `min-level-warn` and `min-level-error`. Messages below it are never formatted, and are optimized away.
+//!  - Without any of these features, the minimum level is `info`. When the calling crate is compiled with
+//!    `cargo test`, the minimum level is `trace` instead, unless one of the features is enabled.
+//!  - The feature `json` emits each message as `{"level":"info","message":"..."}`.
+//!  - When the calling crate is compiled with `cargo test`, messages are printed with `println!` and
+//!    stored in a buffer that tests can inspect with `take_captured`.
+//!
+//! The macro expands to `::near_sdk::env::log`, so the calling crate must depend on `near-sdk`.
+//!
+//! A message can start with an ID in brackets. IDs don't change between translations of a lesson,
+//! so tests can check a message without comparing its text. `contract_panic!` does the same for panics:
+//!
+//! ```ignore
+//! contract_log!(info, [entry_removed] "Successfully removed all entries for {}.", &account_id);
+//! contract_panic!([invalid_day] "Invalid day: {}.", day);
+//! ```
+//!
+//! The module `testing` has the assertions that read them back.
+//!
+
+use std::cell::RefCell;
+use std::fmt;
+
+
+/// Severity of a log message
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Trace,
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+impl Level {
+    /// Lowercase name, as used by the macro
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Level::Trace => "trace",
+            Level::Debug => "debug",
+            Level::Info => "info",
+            Level::Warn => "warn",
+            Level::Error => "error",
+        }
+    }
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+
+/// Lowest level that gets emitted, selected by cargo features. `Info` if none is enabled.
+pub const MIN_LEVEL: Level = match FEATURE_LEVEL {
+    Some(level) => level,
+    None => Level::Info,
+};
+
+/// Lowest level that gets captured when the calling crate is compiled for tests. `Trace` if no feature is enabled.
+pub const TEST_MIN_LEVEL: Level = match FEATURE_LEVEL {
+    Some(level) => level,
+    None => Level::Trace,
+};
+
+const FEATURE_LEVEL: Option<Level> = if cfg!(feature = "min-level-error") {
+    Some(Level::Error)
+} else if cfg!(feature = "min-level-warn") {
+    Some(Level::Warn)
+} else if cfg!(feature = "min-level-info") {
+    Some(Level::Info)
+} else if cfg!(feature = "min-level-debug") {
+    Some(Level::Debug)
+} else if cfg!(feature = "min-level-trace") {
+    Some(Level::Trace)
+} else {
+    None
+};
+
+/// Whether messages are emitted as JSON objects
+pub const JSON: bool = cfg!(feature = "json");
+
+
+/// Returns true if messages of this level are emitted.
+///
+/// Since both sides are constants, the compiler removes disabled calls entirely.
+pub const fn enabled(level: Level) -> bool {
+    level as u8 >= MIN_LEVEL as u8
+}
+
+/// Same as enabled, but for the tests of the calling crate.
+pub const fn enabled_in_tests(level: Level) -> bool {
+    level as u8 >= TEST_MIN_LEVEL as u8
+}
+
+
+/// Formats a message the way it will appear in the logs
+pub fn render(level: Level, id: Option<&str>, message: &str) -> String {
+    match (JSON, id) {
+        (true, Some(id)) => format!("{{\"level\":\"{}\",\"id\":\"{}\",\"message\":\"{}\"}}", level, id, escape_json(message)),
+        (true, None) => format!("{{\"level\":\"{}\",\"message\":\"{}\"}}", level, escape_json(message)),
+        (false, Some(id)) => format!("[{}] [{}] {}", level.name().to_uppercase(), id, message),
+        (false, None) => format!("[{}] {}", level.name().to_uppercase(), message),
+    }
+}
+
+fn escape_json(message: &str) -> String {
+    let mut result = String::with_capacity(message.len());
+
+    for c in message.chars() {
+        match c {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            '\t' => result.push_str("\\t"),
+            c if c.is_control() => result.push_str(&format!("\\u{:04x}", c as u32)),
+            c => result.push(c),
+        }
+    }
+
+    result
+}
+
+
+thread_local! {
+    static CAPTURED: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Used by the macro when the calling crate is compiled for tests.
+#[doc(hidden)]
+pub fn capture(line: String) {
+    println!("{}", line);
+    CAPTURED.with(|captured| captured.borrow_mut().push(line));
+}
+
+/// Returns every message logged by this thread's tests, and clears the buffer.
+///
+/// Each test runs in its own thread, so tests don't see each other's messages.
+pub fn take_captured() -> Vec<String> {
+    CAPTURED.with(|captured| captured.take())
+}
+
+/// Returns every message logged by this thread's tests, without clearing the buffer.
+pub fn captured() -> Vec<String> {
+    CAPTURED.with(|captured| captured.borrow().clone())
+}
+
+
+/// Logs a formatted message with a level.
+///
+/// `contract_log!(level, format, arguments...)`, where level is one of `trace`, `debug`, `info`, `warn` or `error`.
+///
+/// `contract_log!(level, [id] format, arguments...)` also tags the message with an ID.
+#[macro_export]
+macro_rules! contract_log {
+    (@emit $level:expr, $id:expr, $($arg:tt)+) => {
+        #[cfg(test)]
+        if $crate::enabled_in_tests($level) {
+            $crate::capture($crate::render($level, $id, &format!($($arg)+)));
+        }
+
+        #[cfg(not(test))]
+        if $crate::enabled($level) {
+            let line: String = $crate::render($level, $id, &format!($($arg)+));
+
+            #[allow(deprecated)]
+            ::near_sdk::env::log(line.as_bytes());
+        }
+    };
+    (@level trace) => { $crate::Level::Trace };
+    (@level debug) => { $crate::Level::Debug };
+    (@level info) => { $crate::Level::Info };
+    (@level warn) => { $crate::Level::Warn };
+    (@level error) => { $crate::Level::Error };
+    ($level:ident, [$id:ident] $($arg:tt)+) => {
+        $crate::contract_log!(@emit $crate::contract_log!(@level $level), Some(stringify!($id)), $($arg)+)
+    };
+    ($level:ident, $($arg:tt)+) => {
+        $crate::contract_log!(@emit $crate::contract_log!(@level $level), None, $($arg)+)
+    };
+}
+
+/// Panics with a formatted message tagged with an ID.
+///
+/// `contract_panic!([id] format, arguments...)` panics with `"[id] message"`.
+/// Tests can check it with `assert_panics!`, whatever language the message is written in.
+#[macro_export]
+macro_rules! contract_panic {
+    ([$id:ident] $($arg:tt)+) => {
+        panic!("[{}] {}", stringify!($id), format_args!($($arg)+))
+    };
+}
+
+
+pub mod testing;
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn levels_are_ordered() {
+        assert!(Level::Trace < Level::Debug);
+        assert!(Level::Debug < Level::Info);
+        assert!(Level::Info < Level::Warn);
+        assert!(Level::Warn < Level::Error);
+
+        assert!(enabled(Level::Error));
+    }
+
+    #[test]
+    pub fn default_levels() {
+        if FEATURE_LEVEL.is_none() {
+            assert_eq!(MIN_LEVEL, Level::Info);
+            assert!(!enabled(Level::Debug));
+            assert!(enabled_in_tests(Level::Trace));
+        } else {
+            assert_eq!(MIN_LEVEL, TEST_MIN_LEVEL);
+        }
+    }
+
+    #[test]
+    pub fn render_message() {
+        let line = render(Level::Warn, None, "something \"odd\"\nhappened");
+
+        if JSON {
+            assert_eq!(line, "{\"level\":\"warn\",\"message\":\"something \\\"odd\\\"\\nhappened\"}");
+        } else {
+            assert_eq!(line, "[WARN] something \"odd\"\nhappened");
+        }
+    }
+
+    #[test]
+    pub fn render_message_with_id() {
+        let line = render(Level::Info, Some("entry_added"), "Added 3 entries");
+
+        if JSON {
+            assert_eq!(line, "{\"level\":\"info\",\"id\":\"entry_added\",\"message\":\"Added 3 entries\"}");
+        } else {
+            assert_eq!(line, "[INFO] [entry_added] Added 3 entries");
+        }
+    }
+
+    #[test]
+    pub fn escape_control_characters() {
+        assert_eq!(escape_json("a\u{1}b\\"), "a\\u0001b\\\\");
+    }
+
+    #[test]
+    pub fn capture_is_per_thread() {
+        capture(String::from("first"));
+        capture(String::from("second"));
+
+        assert_eq!(take_captured(), vec!["first", "second"]);
+        assert!(take_captured().is_empty());
+    }
+}
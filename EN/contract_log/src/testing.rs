@@ -0,0 +1,234 @@
+//! Assertions for logs and panics that don't depend on the language of the message.
+//!
+//! Lessons are translated, so the same contract logs "Invalid day" in one copy and "Dia inválido" in another.
+//! Instead of comparing text, tests check the ID of a message and the arguments formatted into it:
+//!
+//! ```ignore
+//! use contract_log::{assert_logged, assert_panics};
+//!
+//! // contract_log!(info, [entry_added] "Added {} entries for {}.", 3, "alice");
+//! assert_logged!(entry_added, [3, "alice"]);
+//!
+//! // contract_panic!([invalid_day] "Invalid day: {}.", 32);
+//! assert_panics!(invalid_day, Contract::new_entry(32), [32]);
+//! ```
+//!
+//! An argument matches if it appears as a whole word in the message, so `1` doesn't match `10`.
+//!
+
+use std::panic::{self, AssertUnwindSafe};
+
+
+/// A log line or panic message, split into its ID and text
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Message {
+    pub id: Option<String>,
+    pub text: String,
+}
+
+impl Message {
+    /// Reads a line made by `contract_log!` (plain text or JSON), or a message from `contract_panic!`.
+    pub fn parse(line: &str) -> Message {
+        if line.starts_with('{') {
+            return Message {
+                id: json_field(line, "id"),
+                text: json_field(line, "message").unwrap_or_default(),
+            };
+        }
+
+        // Plain text is "[LEVEL] [id] text". Panics don't have the level.
+        let mut rest: &str = line;
+        if let Some((tag, after)) = bracketed(rest) {
+            if tag.chars().all(|c| c.is_ascii_uppercase()) {
+                rest = after;
+            }
+        }
+
+        match bracketed(rest) {
+            Some((id, text)) => Message { id: Some(String::from(id)), text: String::from(text) },
+            None => Message { id: None, text: String::from(rest) },
+        }
+    }
+
+    /// Returns true if every argument appears as a word in the text.
+    pub fn has_args(&self, args: &[String]) -> bool {
+        let words: Vec<&str> = words(&self.text);
+
+        args.iter().all(|arg| words.contains(&arg.as_str()))
+    }
+}
+
+
+// Splits "[tag] rest" into ("tag", "rest")
+fn bracketed(line: &str) -> Option<(&str, &str)> {
+    let line = line.strip_prefix('[')?;
+    let (tag, rest) = line.split_once(']')?;
+
+    if tag.is_empty() || !tag.chars().all(|c| c.is_alphanumeric() || c == '_') {
+        return None;
+    }
+
+    Some((tag, rest.strip_prefix(' ').unwrap_or(rest)))
+}
+
+// Reads a string field from the JSON written by render
+fn json_field(line: &str, field: &str) -> Option<String> {
+    let start: usize = line.find(&format!("\"{}\":\"", field))? + field.len() + 4;
+
+    let mut value = String::new();
+    let mut chars = line[start..].chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => return Some(value),
+            '\\' => match chars.next()? {
+                'n' => value.push('\n'),
+                'r' => value.push('\r'),
+                't' => value.push('\t'),
+                // \u escapes are only used for control characters, which are irrelevant for the checks
+                'u' => {
+                    chars.nth(3)?;
+                },
+                other => value.push(other),
+            },
+            c => value.push(c),
+        }
+    }
+
+    None
+}
+
+// Words of a message, without the punctuation around the arguments
+fn words(text: &str) -> Vec<&str> {
+    text.split(|c: char| c.is_whitespace() || ",;:()[]{}\"'".contains(c))
+        .map(|word| word.trim_end_matches('.'))
+        .filter(|word| !word.is_empty())
+        .collect()
+}
+
+
+/// Runs a function and returns its panic message, or None if it didn't panic.
+pub fn panic_message<F: FnOnce()>(function: F) -> Option<String> {
+    let payload = panic::catch_unwind(AssertUnwindSafe(function)).err()?;
+
+    if let Some(message) = payload.downcast_ref::<String>() {
+        Some(message.clone())
+    } else if let Some(message) = payload.downcast_ref::<&str>() {
+        Some(String::from(*message))
+    } else {
+        Some(String::new())
+    }
+}
+
+/// Every message logged so far by this thread, parsed.
+pub fn logged() -> Vec<Message> {
+    crate::captured().iter().map(|line| Message::parse(line)).collect()
+}
+
+
+/// Used by `assert_panics!`.
+#[doc(hidden)]
+pub fn check_panic<F: FnOnce()>(id: &str, args: &[String], function: F) {
+    let message = match panic_message(function) {
+        Some(line) => Message::parse(&line),
+        None => panic!("expected a panic with id {}, but nothing panicked", id),
+    };
+
+    assert_eq!(message.id.as_deref(), Some(id), "wrong panic id, the message was: {}", message.text);
+    assert!(message.has_args(args), "the panic {} doesn't contain {:?}: {}", id, args, message.text);
+}
+
+/// Used by `assert_logged!`.
+#[doc(hidden)]
+pub fn check_logged(id: &str, args: &[String]) {
+    let messages: Vec<Message> = logged();
+    let mut with_id = messages.iter().filter(|message| message.id.as_deref() == Some(id)).peekable();
+
+    assert!(with_id.peek().is_some(), "nothing was logged with id {}", id);
+    assert!(
+        with_id.any(|message| message.has_args(args)),
+        "no message with id {} contains {:?}",
+        id,
+        args,
+    );
+}
+
+
+/// Asserts that an expression panics through `contract_panic!` with this ID.
+///
+/// `assert_panics!(id, expression)`, or `assert_panics!(id, expression, [arguments...])` to also check
+/// the arguments formatted into the message.
+#[macro_export]
+macro_rules! assert_panics {
+    ($id:ident, $call:expr) => {
+        $crate::assert_panics!($id, $call, [])
+    };
+    ($id:ident, $call:expr, [$($arg:expr),* $(,)?]) => {
+        $crate::testing::check_panic(
+            stringify!($id),
+            &[$(::std::string::ToString::to_string(&$arg)),*],
+            || { $call; },
+        )
+    };
+}
+
+/// Asserts that this thread logged a message with this ID through `contract_log!`.
+///
+/// `assert_logged!(id)`, or `assert_logged!(id, [arguments...])` to also check the arguments formatted into it.
+#[macro_export]
+macro_rules! assert_logged {
+    ($id:ident) => {
+        $crate::assert_logged!($id, [])
+    };
+    ($id:ident, [$($arg:expr),* $(,)?]) => {
+        $crate::testing::check_logged(
+            stringify!($id),
+            &[$(::std::string::ToString::to_string(&$arg)),*],
+        )
+    };
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn parse_plain_text() {
+        let message = Message::parse("[INFO] [entry_added] Added 3 entries for alice.");
+
+        assert_eq!(message.id.as_deref(), Some("entry_added"));
+        assert_eq!(message.text, "Added 3 entries for alice.");
+        assert!(message.has_args(&[String::from("3"), String::from("alice")]));
+        assert!(!message.has_args(&[String::from("30")]));
+
+        assert_eq!(Message::parse("[WARN] no id").id, None);
+        assert_eq!(Message::parse("[invalid_day] Dia inválido: 32").id.as_deref(), Some("invalid_day"));
+    }
+
+    #[test]
+    pub fn parse_json() {
+        let message = Message::parse("{\"level\":\"info\",\"id\":\"quoted\",\"message\":\"say \\\"hi\\\",\\n1\"}");
+
+        assert_eq!(message.id.as_deref(), Some("quoted"));
+        assert_eq!(message.text, "say \"hi\",\n1");
+    }
+
+    #[test]
+    pub fn panics_and_logs() {
+        crate::contract_log!(info, [greeting] "Olá {}, {}.", "mundo", 2);
+        crate::contract_log!(info, "Without an id");
+
+        crate::assert_logged!(greeting, ["mundo", 2]);
+        crate::assert_panics!(bad_value, crate::contract_panic!([bad_value] "Valor inválido: {}", 7), [7]);
+
+        assert_eq!(panic_message(|| {}), None);
+        assert_eq!(panic_message(|| panic!("plain")), Some(String::from("plain")));
+    }
+
+    #[test]
+    #[should_panic(expected = "wrong panic id")]
+    pub fn wrong_panic_id() {
+        crate::assert_panics!(expected_id, crate::contract_panic!([other_id] "text"));
+    }
+}
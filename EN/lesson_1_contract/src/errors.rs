@@ -0,0 +1,31 @@
+use near_sdk::{
+    borsh::{ self, BorshSerialize },
+    FunctionError,
+};
+
+
+/// Errors returned by the counter functions.
+/// 
+/// MaxReached and MinReached mean the counter hit a limit set by the owner. 
+/// Every other variant is a failure of the call itself.
+#[derive(Debug, FunctionError, BorshSerialize)]
+pub enum Errors {
+    MaxReached(i32),
+    MinReached(i32),
+    InvalidBounds(i32, i32),
+    OwnerOnly,
+    Paused,
+}
+
+
+impl std::fmt::Display for Errors {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Errors::MaxReached(max) => write!(f, "Limit reached: counter can't be higher than {}.", max),
+            Errors::MinReached(min) => write!(f, "Limit reached: counter can't be lower than {}.", min),
+            Errors::InvalidBounds(min, max) => write!(f, "Invalid bounds: minimum ({}) is higher than maximum ({}).", min, max),
+            Errors::OwnerOnly => write!(f, "Only owner may call this function."),
+            Errors::Paused => write!(f, "Contract is paused. Counters can't be changed right now."),
+        }
+    }
+}
@@ -0,0 +1,125 @@
+use near_sdk::{
+    AccountId,
+    borsh::{ self, BorshDeserialize, BorshSerialize },
+    collections::Vector,
+    env,
+    IntoStorageKey,
+    serde::{ Deserialize, Serialize },
+};
+
+
+/// A single change of a counter.
+#[derive(BorshDeserialize, BorshSerialize, Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct HistoryEntry {
+    /// Account whose counter changed.
+    pub account_id: AccountId,
+    /// Account that made the change. The owner, for resets.
+    pub actor: AccountId,
+    pub old_value: i32,
+    pub new_value: i32,
+    pub block_index: u64,
+    pub block_timestamp: u64,
+}
+
+
+impl HistoryEntry {
+    /// Creates an entry for a change to the counter of account_id, made by the caller in the current block.
+    pub fn new(account_id: AccountId, old_value: i32, new_value: i32) -> Self {
+        HistoryEntry {
+            account_id,
+            actor: env::predecessor_account_id(),
+            old_value,
+            new_value,
+            block_index: env::block_height(),
+            block_timestamp: env::block_timestamp(),
+        }
+    }
+}
+
+
+/// Bounded list of counter changes.
+/// 
+/// Works as a ring buffer. Once max_length entries are stored, 
+/// each new entry replaces the oldest one.
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct History {
+    entries: Vector<HistoryEntry>,
+    // Position of the oldest entry in entries.
+    oldest: u64,
+    max_length: u64,
+}
+
+
+impl History {
+    pub fn new<S: IntoStorageKey>(prefix: S, max_length: u64) -> Self {
+        History {
+            entries: Vector::new(prefix),
+            oldest: 0,
+            max_length,
+        }
+    }
+
+    pub fn len(&self) -> u64 {
+        self.entries.len()
+    }
+
+    pub fn max_length(&self) -> u64 {
+        self.max_length
+    }
+
+    /// Stores an entry, evicting the oldest one if the history is full.
+    pub fn push(&mut self, entry: &HistoryEntry) {
+        let length: u64 = self.len();
+
+        if self.max_length == 0 {
+            return;
+        }
+
+        if length < self.max_length {
+            self.entries.push(entry);
+            return;
+        }
+
+        self.entries.replace(self.oldest, entry);
+        self.oldest = (self.oldest + 1) % length;
+    }
+
+    /// Returns the entry at index, where 0 is the newest entry.
+    pub fn get(&self, index: u64) -> Option<HistoryEntry> {
+        let length: u64 = self.len();
+
+        if index >= length {
+            return None;
+        }
+
+        // Position counting from the oldest entry.
+        let position: u64 = length - 1 - index;
+
+        self.entries.get((self.oldest + position) % length)
+    }
+
+    /// Returns up to limit entries, newest first, skipping the first from_index entries.
+    pub fn get_page(&self, from_index: u64, limit: u64) -> Vec<HistoryEntry> {
+        (from_index..self.len().min(from_index.saturating_add(limit)))
+            .filter_map(|index| self.get(index))
+            .collect()
+    }
+
+    /// Changes the maximum length, evicting the oldest entries that don't fit.
+    pub fn set_max_length(&mut self, max_length: u64) {
+        let kept: u64 = self.len().min(max_length);
+
+        // Newest first, so reverse it to push back from the oldest.
+        let mut entries: Vec<HistoryEntry> = self.get_page(0, kept);
+        entries.reverse();
+
+        self.entries.clear();
+        for entry in entries.iter() {
+            self.entries.push(entry);
+        }
+
+        self.oldest = 0;
+        self.max_length = max_length;
+    }
+}
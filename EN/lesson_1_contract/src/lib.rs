@@ -17,48 +17,308 @@ use near_sdk::{
         BorshDeserialize,
         BorshSerialize,
     },
-    // env,
+    // An account id, like "stiltztinkerstein.near"
+    AccountId,
+    // Used for deriving unique prefixes for our collections
+    BorshStorageKey,
+    // Persistent collections, each one is stored under its own prefix in the trie
+    collections::LookupMap,
+    // Information about the execution environment, like who is calling the contract
+    env,
     // Creates boilerplatecode needed for NEAR virtual machine
     near_bindgen,
 };
 
-near_sdk::setup_alloc!();
+mod errors;
+mod history;
+
+pub use errors::Errors;
+pub use history::HistoryEntry;
+
+use history::History;
+
+
+/// Keys used for storing collections in the trie.
+/// 
+/// Each collection needs a unique prefix, so we use this enum as key.
+#[derive(BorshStorageKey, BorshSerialize)]
+enum StorageKey {
+    Counters,
+    History,
+}
+
+
+/// How many changes are kept in the history, unless the owner changes it.
+const DEFAULT_HISTORY_LENGTH: u64 = 100;
+
+
+/// Layout of the state before counters were stored per account.
+/// 
+/// Only used by migrate to read the old state.
+#[derive(BorshDeserialize)]
+struct OldContract {
+    counter: i32,
+}
+
 
 /// Smart Contract. This struct contains the state in the VM.
 /// Functions here are Smart Contract functions.
 #[near_bindgen]
 #[derive(BorshDeserialize, BorshSerialize)]
 pub struct Contract{
-    /// Counter.
-    counter: i32
+    /// Account allowed to call the admin functions.
+    owner: AccountId,
+    /// While true, counters can't be changed.
+    paused: bool,
+    /// One counter for each account that called the contract.
+    counters: LookupMap<AccountId, i32>,
+    /// Lowest value a counter can be decreased to.
+    min: i32,
+    /// Highest value a counter can be increased to.
+    max: i32,
+    /// Latest changes made to the counters.
+    history: History,
 }
 
-// Default is used for auto-initialization
+// Default is used for auto-initialization. The contract account is the owner.
 impl Default for Contract{
     fn default() -> Self{
-        Contract { counter: 0 }
+        Contract { 
+            owner: env::current_account_id(),
+            paused: false,
+            counters: LookupMap::new(StorageKey::Counters),
+            min: i32::MIN,
+            max: i32::MAX,
+            history: History::new(StorageKey::History, DEFAULT_HISTORY_LENGTH),
+        }
     }
 }
 
 #[near_bindgen]
 impl Contract{
 
-    /// Returns the counter value
+    /// Initializes the contract with the given owner.
+    #[init]
+    pub fn new(owner_id: AccountId) -> Self {
+        Contract {
+            owner: owner_id,
+            ..Contract::default()
+        }
+    }
+
+    /// Converts a state with a single global counter into the per-account layout.
+    /// 
+    /// The old counter is kept as the counter of the contract account itself.
+    /// 
+    /// Only the contract account can call this function.
+    /// 
+    /// # Panics
+    ///  - If there's no state stored.
+    /// 
+    #[private]
+    #[init(ignore_state)]
+    pub fn migrate() -> Self {
+        let old: OldContract = env::state_read().expect("Failed to read old state.");
+
+        let mut contract = Contract::default();
+        contract.counters.insert(&env::current_account_id(), &old.counter);
+
+        contract
+    }
+
+    fn assert_owner(&self) -> Result<(), Errors> {
+        if env::predecessor_account_id() != self.owner {
+            return Err(Errors::OwnerOnly);
+        }
+
+        Ok(())
+    }
+
+    fn assert_not_paused(&self) -> Result<(), Errors> {
+        if self.paused {
+            return Err(Errors::Paused);
+        }
+
+        Ok(())
+    }
+
+    // Stores the counter for the caller, records the change and returns it.
+    fn save(&mut self, counter: i32) -> i32 {
+        self.save_for(&env::predecessor_account_id(), counter)
+    }
+
+    // Stores the counter for the given account, records the change and returns it.
+    fn save_for(&mut self, account_id: &AccountId, counter: i32) -> i32 {
+        let old_value: i32 = self.counters.insert(account_id, &counter).unwrap_or(0);
+
+        self.history.push(&HistoryEntry::new(account_id.clone(), old_value, counter));
+
+        counter
+    }
+
+    /// Returns the account allowed to call the admin functions.
+    pub fn get_owner(&self) -> AccountId {
+        self.owner.clone()
+    }
+
+    /// Gives the admin role to another account.
+    /// 
+    /// Only the owner can call this function.
+    #[handle_result]
+    pub fn transfer_ownership(&mut self, new_owner: AccountId) -> Result<(), Errors> {
+        self.assert_owner()?;
+
+        self.owner = new_owner;
+
+        Ok(())
+    }
+
+    /// Returns true if counters can't be changed right now.
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Stops (true) or resumes (false) changes to the counters.
+    /// 
+    /// Only the owner can call this function.
+    #[handle_result]
+    pub fn set_paused(&mut self, paused: bool) -> Result<(), Errors> {
+        self.assert_owner()?;
+
+        self.paused = paused;
+
+        Ok(())
+    }
+
+    /// Sets the counter of the given account back to 0.
+    /// 
+    /// Only the owner can call this function.
+    #[handle_result]
+    pub fn reset(&mut self, account_id: AccountId) -> Result<(), Errors> {
+        self.assert_owner()?;
+
+        self.save_for(&account_id, 0);
+
+        Ok(())
+    }
+
+    /// Returns the counter value of the caller
     pub fn get(&self) -> i32 {
-        // return self.counter;
-        self.counter
+        self.get_for(env::predecessor_account_id())
+    }
+
+    /// Returns the counter value of any account. 
+    /// 
+    /// Accounts that never called the contract have a counter of 0.
+    pub fn get_for(&self, account_id: AccountId) -> i32 {
+        self.counters.get(&account_id).unwrap_or(0)
+    }
+
+    /// Returns the lowest and highest values a counter can have.
+    pub fn get_bounds(&self) -> (i32, i32) {
+        (self.min, self.max)
+    }
+
+    /// Changes the lowest and highest values a counter can have.
+    /// 
+    /// Counters that are already out of bounds are kept as they are, 
+    /// but can only move back towards the bounds.
+    /// 
+    /// Only the owner can call this function.
+    #[handle_result]
+    pub fn set_bounds(&mut self, min: i32, max: i32) -> Result<(), Errors> {
+        self.assert_owner()?;
+
+        if min > max {
+            return Err(Errors::InvalidBounds(min, max));
+        }
+
+        self.min = min;
+        self.max = max;
+
+        Ok(())
+    }
+
+    /// Returns up to limit changes made to the counters, newest first.
+    /// 
+    /// from_index is how many of the newest changes to skip.
+    pub fn get_history(&self, from_index: u64, limit: u64) -> Vec<HistoryEntry> {
+        self.history.get_page(from_index, limit)
+    }
+
+    /// Returns how many changes are kept in the history.
+    pub fn get_history_max_length(&self) -> u64 {
+        self.history.max_length()
+    }
+
+    /// Changes how many changes are kept in the history. 
+    /// 
+    /// If the history is longer than max_length, the oldest changes are removed.
+    /// 
+    /// Only the owner can call this function.
+    #[handle_result]
+    pub fn set_history_max_length(&mut self, max_length: u64) -> Result<(), Errors> {
+        self.assert_owner()?;
+
+        self.history.set_max_length(max_length);
+
+        Ok(())
+    }
+
+    /// Increments the counter of the caller by 1
+    #[handle_result]
+    pub fn increment(&mut self) -> Result<i32, Errors> {
+        self.increment_by(1)
+    }
+
+    /// Decreases the counter of the caller by 1
+    #[handle_result]
+    pub fn decrement(&mut self) -> Result<i32, Errors> {
+        self.decrement_by(1)
     }
 
-    /// Increments the counter by 1
-    pub fn increment(&mut self) -> i32 {
-        self.counter += 1;
-        self.counter
+    /// Increments the counter of the caller by n.
+    /// 
+    /// # Errors
+    ///  - MaxReached: if the result would be higher than the maximum bound.
+    ///  - Paused: if the owner paused the contract.
+    #[handle_result]
+    pub fn increment_by(&mut self, n: u32) -> Result<i32, Errors> {
+        self.assert_not_paused()?;
+
+        // i64 holds any i32 plus any u32, so the sum itself can't overflow
+        let counter: i64 = i64::from(self.get()) + i64::from(n);
+
+        if counter > i64::from(self.max) {
+            return Err(Errors::MaxReached(self.max));
+        }
+
+        // max and min are i32, so after the bound check the result always fits
+        let counter: i32 = counter as i32;
+
+        Ok(self.save(counter))
     }
 
-    /// Decreases the counter by 1
-    pub fn decrement(&mut self) -> i32 {
-        self.counter -= 1;
-        self.counter
+    /// Decreases the counter of the caller by n.
+    /// 
+    /// # Errors
+    ///  - MinReached: if the result would be lower than the minimum bound.
+    ///  - Paused: if the owner paused the contract.
+    #[handle_result]
+    pub fn decrement_by(&mut self, n: u32) -> Result<i32, Errors> {
+        self.assert_not_paused()?;
+
+        // i64 holds any i32 minus any u32, so the difference itself can't overflow
+        let counter: i64 = i64::from(self.get()) - i64::from(n);
+
+        if counter < i64::from(self.min) {
+            return Err(Errors::MinReached(self.min));
+        }
+
+        // max and min are i32, so after the bound check the result always fits
+        let counter: i32 = counter as i32;
+
+        Ok(self.save(counter))
     }
 }
 
@@ -77,19 +337,19 @@ mod tests{
         // e.g.: we wanted to know the user account that executed this contract
         // we would use a function found here
         env,
-        // Mocks (simulates) the Blockchain
-        MockedBlockchain,
         // Macro that sets up the test environment with a valid context
         testing_env,
         // Used to create a test context
         test_utils::VMContextBuilder,
-        // A valid account id
-        // An account id is a string, but the entire string does not represent the actual valid id
-        json_types::ValidAccountId,
     };
 
     /// This function is not a test. It is used by the tests to setup our mock test environment
     fn env_setup(){
+        env_setup_with_caller("stiltztinkerstein");
+    }
+
+    /// Same as env_setup, but the contract is called by the given account.
+    fn env_setup_with_caller(predecessor: &str){
         // Initializes a context builder for our tests 
         let mut builder: VMContextBuilder = VMContextBuilder::new();
 
@@ -109,16 +369,14 @@ mod tests{
         // random_seed
         // is_view
 
-        // account id string
-        let account_id: AccountId = String::from("stiltztinkerstein");
+        // parse tries to convert a string to a valid account id
+        // panics if the id is invalid
+        let account_id: AccountId = "stiltztinkerstein".parse().unwrap();
 
-        builder.current_account_id(
-            // try_from tries to convert a string to a valid account id
-            // panics if the id is invalid
-            ValidAccountId::try_from(
-                account_id.clone()
-            ).unwrap()
-        );
+        builder.current_account_id(account_id.clone());
+
+        // the account calling the contract
+        builder.predecessor_account_id(predecessor.parse().unwrap());
 
         // sets up the mock
         testing_env!(builder.build());
@@ -155,7 +413,7 @@ mod tests{
 
         let mut contract: Contract = Contract::default();
 
-        contract.increment();
+        contract.increment().unwrap();
 
         assert_eq!(
             contract.get(),
@@ -169,11 +427,253 @@ mod tests{
 
         let mut contract: Contract = Contract::default();
 
-        contract.decrement();
+        contract.decrement().unwrap();
 
         assert_eq!(
             contract.get(),
             -1
         );
     }
+
+    #[test]
+    pub fn counters_per_account() {
+        env_setup_with_caller("alice");
+
+        let mut contract: Contract = Contract::default();
+
+        contract.increment().unwrap();
+        contract.increment().unwrap();
+
+        env_setup_with_caller("bob");
+
+        contract.decrement().unwrap();
+
+        assert_eq!(contract.get(), -1);
+        assert_eq!(contract.get_for("alice".parse().unwrap()), 2);
+        assert_eq!(contract.get_for("bob".parse().unwrap()), -1);
+        assert_eq!(contract.get_for("carol".parse().unwrap()), 0);
+    }
+
+    #[test]
+    pub fn migrate() {
+        env_setup();
+
+        // Write the state the way the single counter contract would have stored it.
+        #[derive(BorshSerialize)]
+        struct OldState {
+            counter: i32,
+        }
+
+        env::state_write(&OldState { counter: 7 });
+
+        let contract: Contract = Contract::migrate();
+
+        assert_eq!(contract.get_for("stiltztinkerstein".parse().unwrap()), 7);
+    }
+
+    #[test]
+    pub fn increment_by_and_decrement_by() {
+        env_setup();
+
+        let mut contract: Contract = Contract::default();
+
+        assert_eq!(contract.increment_by(10).unwrap(), 10);
+        assert_eq!(contract.decrement_by(15).unwrap(), -5);
+    }
+
+    #[test]
+    pub fn bounds() {
+        env_setup();
+
+        let mut contract: Contract = Contract::default();
+        contract.set_bounds(-1, 2).unwrap();
+
+        assert_eq!(contract.get_bounds(), (-1, 2));
+        assert!(matches!(contract.increment_by(3), Err(Errors::MaxReached(2))));
+        assert!(matches!(contract.decrement_by(2), Err(Errors::MinReached(-1))));
+        assert!(matches!(contract.set_bounds(3, 2), Err(Errors::InvalidBounds(3, 2))));
+
+        // Rejected calls don't change the counter
+        assert_eq!(contract.get(), 0);
+    }
+
+    #[test]
+    pub fn set_bounds_owner_only() {
+        env_setup_with_caller("alice");
+
+        let mut contract: Contract = Contract::default();
+
+        assert!(matches!(contract.set_bounds(0, 1), Err(Errors::OwnerOnly)));
+    }
+
+    #[test]
+    pub fn limits_of_i32() {
+        env_setup();
+
+        let mut contract: Contract = Contract::default();
+
+        // the default bounds are the limits of i32, reaching them isn't an overflow
+        contract.increment_by(i32::MAX as u32).unwrap();
+        assert!(matches!(contract.increment(), Err(Errors::MaxReached(i32::MAX))));
+
+        // n doesn't fit in an i32, but the result does
+        assert_eq!(contract.decrement_by(u32::MAX).unwrap(), i32::MIN);
+        assert!(matches!(contract.decrement(), Err(Errors::MinReached(i32::MIN))));
+        assert_eq!(contract.increment_by(u32::MAX).unwrap(), i32::MAX);
+
+        contract.reset("stiltztinkerstein".parse().unwrap()).unwrap();
+        assert_eq!(contract.decrement_by(2_147_483_648).unwrap(), i32::MIN);
+    }
+
+    #[test]
+    pub fn history() {
+        env_setup_with_caller("alice");
+
+        let mut contract: Contract = Contract::default();
+
+        contract.increment_by(5).unwrap();
+
+        env_setup_with_caller("bob");
+
+        contract.decrement().unwrap();
+        contract.increment().unwrap();
+
+        let history: Vec<HistoryEntry> = contract.get_history(0, 10);
+        let changes: Vec<(String, i32, i32)> = history
+            .iter()
+            .map(|entry| (entry.account_id.to_string(), entry.old_value, entry.new_value))
+            .collect();
+
+        // everyone changed their own counter
+        assert!(history.iter().all(|entry| entry.actor == entry.account_id));
+
+        assert_eq!(
+            changes, 
+            vec![
+                (String::from("bob"), -1, 0),
+                (String::from("bob"), 0, -1),
+                (String::from("alice"), 0, 5),
+            ],
+        );
+
+        // Pagination
+        assert_eq!(contract.get_history(1, 1), vec![history[1].clone()]);
+        assert_eq!(contract.get_history(3, 10), vec![]);
+    }
+
+    #[test]
+    pub fn history_max_length() {
+        env_setup();
+
+        let mut contract: Contract = Contract::default();
+        contract.set_history_max_length(3).unwrap();
+
+        for _ in 0..5 {
+            contract.increment().unwrap();
+        }
+
+        let new_values: Vec<i32> = contract
+            .get_history(0, 10)
+            .iter()
+            .map(|entry| entry.new_value)
+            .collect();
+
+        assert_eq!(new_values, vec![5, 4, 3]);
+
+        // Shrinking removes the oldest changes
+        contract.set_history_max_length(2).unwrap();
+        contract.increment().unwrap();
+
+        let new_values: Vec<i32> = contract
+            .get_history(0, 10)
+            .iter()
+            .map(|entry| entry.new_value)
+            .collect();
+
+        assert_eq!(new_values, vec![6, 5]);
+        assert_eq!(contract.get_history_max_length(), 2);
+
+        env_setup_with_caller("alice");
+
+        assert!(matches!(contract.set_history_max_length(10), Err(Errors::OwnerOnly)));
+    }
+
+    #[test]
+    pub fn new() {
+        env_setup();
+
+        let contract: Contract = Contract::new("alice".parse().unwrap());
+
+        assert_eq!(contract.get_owner().as_str(), "alice");
+        assert!(!contract.is_paused());
+    }
+
+    #[test]
+    pub fn reset() {
+        env_setup_with_caller("alice");
+
+        let mut contract: Contract = Contract::new("alice".parse().unwrap());
+
+        env_setup_with_caller("bob");
+
+        contract.increment_by(3).unwrap();
+
+        // bob is not the owner
+        assert!(matches!(contract.reset("bob".parse().unwrap()), Err(Errors::OwnerOnly)));
+        assert_eq!(contract.get(), 3);
+
+        env_setup_with_caller("alice");
+
+        contract.reset("bob".parse().unwrap()).unwrap();
+
+        assert_eq!(contract.get_for("bob".parse().unwrap()), 0);
+
+        // the change is to bob's counter, made by alice
+        let entry: HistoryEntry = contract.get_history(0, 1).remove(0);
+        assert_eq!(entry.account_id.as_str(), "bob");
+        assert_eq!(entry.actor.as_str(), "alice");
+        assert_eq!((entry.old_value, entry.new_value), (3, 0));
+    }
+
+    #[test]
+    pub fn transfer_ownership() {
+        env_setup_with_caller("alice");
+
+        let mut contract: Contract = Contract::new("alice".parse().unwrap());
+
+        contract.transfer_ownership("bob".parse().unwrap()).unwrap();
+
+        assert_eq!(contract.get_owner().as_str(), "bob");
+
+        // alice isn't the owner anymore
+        assert!(matches!(contract.transfer_ownership("alice".parse().unwrap()), Err(Errors::OwnerOnly)));
+
+        env_setup_with_caller("bob");
+
+        contract.transfer_ownership("carol".parse().unwrap()).unwrap();
+
+        assert_eq!(contract.get_owner().as_str(), "carol");
+    }
+
+    #[test]
+    pub fn pause() {
+        env_setup_with_caller("alice");
+
+        let mut contract: Contract = Contract::new("alice".parse().unwrap());
+
+        contract.set_paused(true).unwrap();
+
+        assert!(matches!(contract.increment(), Err(Errors::Paused)));
+        assert!(matches!(contract.decrement(), Err(Errors::Paused)));
+
+        env_setup_with_caller("bob");
+
+        assert!(matches!(contract.set_paused(false), Err(Errors::OwnerOnly)));
+
+        env_setup_with_caller("alice");
+
+        contract.set_paused(false).unwrap();
+
+        assert_eq!(contract.increment().unwrap(), 1);
+    }
 }
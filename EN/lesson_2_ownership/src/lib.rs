@@ -1,12 +1,53 @@
 use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
 #[allow(unused_imports)]
 use near_sdk::{env, near_bindgen};
+use near_sdk::{AccountId, Balance, Promise, StorageUsage};
+use near_sdk::collections::LookupMap;
+use near_sdk::serde::{Deserialize, Serialize};
+// Splits a string into what a user sees as single characters
+use unicode_segmentation::UnicodeSegmentation;
 
 
 // A Macro that generates boilerplate code for NEAR. For v.4 setup_alloc() will be deprecated. 
 near_sdk::setup_alloc!();
 
 
+/// Highest number of graphemes (characters as seen by the user) a name can have.
+const MAX_NAME_LENGTH: usize = 64;
+
+/// Storage key for the bytes each account paid for.
+/// 
+/// It's not a field of Contract, so the state written by older versions can still be read.
+const STORAGE_PAID_KEY: &[u8] = b"p";
+
+
+/// Different ways of measuring the size of a string.
+/// 
+/// "São Paulo" has 10 bytes, because "ã" needs 2 bytes in UTF-8, but only 9 chars and 9 graphemes.
+/// 
+/// "é" can also be written as "e" followed by a combining accent, which is 2 chars but still 1 grapheme.
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct NameMetrics {
+    /// Length in bytes, encoded as UTF-8. This is what String::len returns.
+    pub bytes: u32,
+    /// Number of unicode scalar values (char).
+    pub chars: u32,
+    /// Number of extended grapheme clusters.
+    pub graphemes: u32,
+}
+
+impl From<&str> for NameMetrics {
+    fn from(name: &str) -> Self {
+        NameMetrics {
+            bytes: name.len() as u32,
+            chars: name.chars().count() as u32,
+            graphemes: name.graphemes(true).count() as u32,
+        }
+    }
+}
+
+
 #[near_bindgen]
 #[derive(Clone, BorshDeserialize, BorshSerialize)]
 pub struct Contract {
@@ -69,12 +110,16 @@ impl Contract{
     }
 
 
-    /// Return the size of the string stored in the name variable, but change its value
-    pub fn get_length_again(&mut self) -> u32 {
+    /// Return the size of the string stored in the name variable, after changing a copy of it
+    pub fn get_length_again(&self) -> u32 {
+        // A copy that this function owns. Changing the stored name costs storage, 
+        // so only set_name is allowed to do it.
+        let mut name: String = self.name.clone();
+
         // we can declare variables that store references to another value
-        let a_reference: &String = &self.name;
-        let _another_reference: &String = &self.name;
-        let _yet_another_reference: &String = &self.name;
+        let a_reference: &String = &name;
+        let _another_reference: &String = &name;
+        let _yet_another_reference: &String = &name;
 
 
         // We can have many immutable references
@@ -82,16 +127,119 @@ impl Contract{
         // If we needed a mutable reference, you can't have any existing immutable references
 
         // Uncomment the following line to raise an error: existing borrow
-        // self.name = String::from("Changed name");
+        // name = String::from("Changed name");
 
         let length = Self::this_takes_a_reference(a_reference);
 
         // this next line is Ok, since a reference is no longer used (borrow)
         // and the compiler can drop the borrow
-        self.name = String::from("Changed name");
+        name = String::from("Changed name");
+
+        env::log(format!("The copy of name is now \"{}\"", name).as_bytes());
 
         length as u32
     }
+
+    /// Returns the name stored
+    pub fn get_name(&self) -> String {
+        self.name.clone()
+    }
+
+    /// Changes the name stored.
+    /// 
+    /// The caller pays for the storage used by a longer name, by attaching a deposit. 
+    /// Any deposit left after paying is sent back.
+    /// 
+    /// When the new name is shorter, the storage freed is paid back to the caller, 
+    /// along with the attached deposit. Only bytes that the caller paid for before are paid back, 
+    /// the storage of the default name was paid by the contract.
+    /// 
+    /// # Panics
+    ///  - If name is empty or only whitespace.
+    ///  - If name has more than 64 graphemes.
+    ///  - If name has control characters (like line breaks).
+    ///  - If the attached deposit doesn't cover the storage used.
+    /// 
+    #[payable]
+    pub fn set_name(&mut self, name: String) {
+        assert!(!name.trim().is_empty(), "Name can't be empty.");
+
+        let graphemes: usize = name.graphemes(true).count();
+        assert!(
+            graphemes <= MAX_NAME_LENGTH, 
+            "Name can't be longer than {} characters. Got {}.", MAX_NAME_LENGTH, graphemes,
+        );
+
+        assert!(
+            !name.chars().any(char::is_control), 
+            "Name can't have control characters.",
+        );
+
+        let initial_storage: StorageUsage = env::storage_usage();
+
+        self.name = name;
+
+        // The state is only written after the function returns, 
+        // so we write it now to measure how much storage it uses.
+        env::state_write(self);
+
+        let name_storage: StorageUsage = env::storage_usage();
+        let attached_deposit: Balance = env::attached_deposit();
+
+        let payer: AccountId = env::predecessor_account_id();
+        let mut storage_paid: LookupMap<AccountId, StorageUsage> = Self::storage_paid();
+        let paid: StorageUsage = storage_paid.get(&payer).unwrap_or(0);
+
+        let refund: Balance = if name_storage >= initial_storage {
+            // The caller also pays for the bytes used to remember the payment, 
+            // so the entry is written before measuring. A u64 always has the same size.
+            storage_paid.insert(&payer, &(paid + name_storage - initial_storage));
+
+            let final_storage: StorageUsage = env::storage_usage();
+            let used: StorageUsage = final_storage - initial_storage;
+            storage_paid.insert(&payer, &(paid + used));
+
+            let cost: Balance = Balance::from(used) * env::storage_byte_cost();
+
+            assert!(
+                attached_deposit >= cost, 
+                "Attached deposit of {} yoctoNEAR doesn't cover the storage cost of {} yoctoNEAR.", 
+                attached_deposit, cost,
+            );
+
+            attached_deposit - cost
+        } else {
+            // Bytes that someone else paid for, or that the contract paid for, are not refunded
+            let released: StorageUsage = (initial_storage - name_storage).min(paid);
+
+            if released == paid {
+                storage_paid.remove(&payer);
+            } else {
+                storage_paid.insert(&payer, &(paid - released));
+            }
+
+            attached_deposit + Balance::from(released) * env::storage_byte_cost()
+        };
+
+        if refund > 0 {
+            Promise::new(env::predecessor_account_id()).transfer(refund);
+        }
+    }
+
+    /// Returns how many bytes of storage the account paid for and can still get back.
+    pub fn get_storage_paid(&self, account_id: AccountId) -> StorageUsage {
+        Self::storage_paid().get(&account_id).unwrap_or(0)
+    }
+
+    /// Returns the size of the name in bytes, chars and graphemes.
+    pub fn get_name_metrics(&self) -> NameMetrics {
+        NameMetrics::from(&self.name[..])
+    }
+
+    // Bytes of storage paid by each account. 
+    fn storage_paid() -> LookupMap<AccountId, StorageUsage> {
+        LookupMap::new(STORAGE_PAID_KEY.to_vec())
+    }
 }
 
 #[cfg(test)]
@@ -106,7 +254,22 @@ mod tests{
         json_types::ValidAccountId,
     };
 
+    // 1 NEAR in yoctoNEAR, enough for the storage of any name in these tests
+    const DEPOSIT: Balance = 1_000_000_000_000_000_000_000_000;
+
     fn env_setup(){
+        env_setup_with_deposit(0);
+    }
+
+    // Same as env_setup, but the call has the given deposit attached.
+    fn env_setup_with_deposit(deposit: Balance){
+        let account_id: AccountId = String::from("alice");
+
+        env_setup_as(account_id, deposit);
+    }
+
+    // Same as env_setup_with_deposit, but the call is made by predecessor.
+    fn env_setup_as(predecessor: AccountId, deposit: Balance){
         let mut builder: VMContextBuilder = VMContextBuilder::new();
 
         // fields that can be changed via the builder
@@ -133,6 +296,14 @@ mod tests{
             ).unwrap()
         );
 
+        builder.predecessor_account_id(
+            ValidAccountId::try_from(
+                predecessor.clone()
+            ).unwrap()
+        );
+
+        builder.attached_deposit(deposit);
+
         testing_env!(builder.build());
 
         assert_eq!(
@@ -148,7 +319,7 @@ mod tests{
     pub fn get_length() {
         env_setup();
     
-        let mut contract: Contract = Contract::default();
+        let contract: Contract = Contract::default();
     
         // Both functions to the same thing, so both must return the same value
         assert_eq!(
@@ -156,10 +327,150 @@ mod tests{
             contract.get_length_again()
         );
 
-        // get_length_again also modified the stored string value or the name field
+        // get_length_again only changed a copy, the stored name is the same
         assert_eq!(
             contract.name,
-            "Changed name"
+            "A default string"
         );
     }
-}
\ No newline at end of file
+
+    #[test]
+    pub fn name_metrics_pt_br() {
+        env_setup_with_deposit(DEPOSIT);
+
+        let mut contract: Contract = Contract::default();
+
+        contract.set_name(String::from("São Paulo"));
+
+        assert_eq!(
+            contract.get_name_metrics(),
+            NameMetrics { bytes: 10, chars: 9, graphemes: 9 },
+        );
+
+        // "ção" with a combining tilde: "a" followed by U+0303
+        contract.set_name(String::from("Coraç\u{61}\u{303}o"));
+
+        assert_eq!(
+            contract.get_name_metrics(),
+            NameMetrics { bytes: 10, chars: 8, graphemes: 7 },
+        );
+    }
+
+    #[test]
+    pub fn name_metrics_es() {
+        env_setup_with_deposit(DEPOSIT);
+
+        let mut contract: Contract = Contract::default();
+
+        contract.set_name(String::from("Año Nuevo en España"));
+
+        assert_eq!(
+            contract.get_name_metrics(),
+            NameMetrics { bytes: 21, chars: 19, graphemes: 19 },
+        );
+
+        // Flag made of two regional indicators, each one 4 bytes long
+        contract.set_name(String::from("¡Olé! 🇪🇸"));
+
+        assert_eq!(
+            contract.get_name_metrics(),
+            NameMetrics { bytes: 16, chars: 8, graphemes: 7 },
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Name can't be empty.")]
+    pub fn set_name_empty() {
+        env_setup();
+
+        let mut contract: Contract = Contract::default();
+
+        contract.set_name(String::from("   "));
+    }
+
+    #[test]
+    #[should_panic(expected = "Name can't have control characters.")]
+    pub fn set_name_control_characters() {
+        env_setup();
+
+        let mut contract: Contract = Contract::default();
+
+        contract.set_name(String::from("São\nPaulo"));
+    }
+
+    #[test]
+    #[should_panic(expected = "Name can't be longer than 64 characters. Got 65.")]
+    pub fn set_name_too_long() {
+        env_setup();
+
+        let mut contract: Contract = Contract::default();
+
+        // 65 graphemes, each one with 2 bytes
+        contract.set_name("ç".repeat(65));
+    }
+
+    #[test]
+    #[should_panic(expected = "doesn't cover the storage cost")]
+    pub fn set_name_without_deposit() {
+        env_setup();
+
+        let mut contract: Contract = Contract::default();
+
+        contract.set_name(String::from("A name longer than the default string"));
+    }
+
+    #[test]
+    pub fn set_name_shorter_without_deposit() {
+        env_setup_with_deposit(DEPOSIT);
+
+        let mut contract: Contract = Contract::default();
+
+        contract.set_name(String::from("A name longer than the default string"));
+
+        // A shorter name frees storage, so no deposit is needed
+        env_setup();
+
+        contract.set_name(String::from("Short"));
+
+        assert_eq!(contract.get_name(), "Short");
+    }
+
+    #[test]
+    pub fn storage_refund_only_for_paid_bytes() {
+        let alice: AccountId = String::from("alice");
+        let bob: AccountId = String::from("bob");
+
+        env_setup_as(bob.clone(), 0);
+
+        let mut contract: Contract = Contract::default();
+        env::state_write(&contract);
+
+        // bob didn't pay for the default name, so nothing is paid back to him
+        contract.set_name(String::from("Short"));
+
+        assert_eq!(contract.get_storage_paid(bob.clone()), 0);
+
+        env_setup_as(alice.clone(), DEPOSIT);
+        env::state_write(&contract);
+
+        contract.set_name(String::from("A name longer than the default string"));
+
+        let paid: StorageUsage = contract.get_storage_paid(alice.clone());
+        assert!(paid > 32, "alice paid for {} bytes", paid);
+
+        // Another shorter name by bob, alice's bytes are still hers
+        env_setup_as(bob.clone(), 0);
+
+        contract.set_name(String::from("A name"));
+
+        assert_eq!(contract.get_storage_paid(bob), 0);
+        assert_eq!(contract.get_storage_paid(alice.clone()), paid);
+
+        // alice gets back the bytes released by her shorter name
+        env_setup_as(alice.clone(), 0);
+
+        contract.set_name(String::from("A"));
+
+        assert_eq!(contract.get_storage_paid(alice), paid - 5);
+    }
+}
@@ -0,0 +1,32 @@
+use near_sdk::{
+    borsh::{ self, BorshSerialize },
+    FunctionError,
+};
+
+
+/// Errors returned when changing StructExample.
+#[derive(Debug, FunctionError, BorshSerialize)]
+pub enum Errors {
+    /// Name of the field that received NaN or ±Infinity.
+    NotFinite(String),
+    /// Name of the field, value, minimum and maximum.
+    IntegerOutOfRange(String, i64, i64, i64),
+    /// Name of the field, value, minimum and maximum.
+    FloatOutOfRange(String, f32, f32, f32),
+    /// Name of the field whose range has a minimum higher than the maximum, or isn't finite.
+    InvalidRange(String),
+    OwnerOnly,
+}
+
+
+impl std::fmt::Display for Errors {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Errors::NotFinite(field) => write!(f, "{} must be a finite number. NaN and Infinity are not allowed.", field),
+            Errors::IntegerOutOfRange(field, value, min, max) => write!(f, "{} must be between {} and {}. Got {}.", field, min, max, value),
+            Errors::FloatOutOfRange(field, value, min, max) => write!(f, "{} must be between {} and {}. Got {}.", field, min, max, value),
+            Errors::InvalidRange(field) => write!(f, "Invalid range for {}. Minimum must not be higher than maximum, and both must be finite.", field),
+            Errors::OwnerOnly => write!(f, "Only owner may call this function."),
+        }
+    }
+}
@@ -1,21 +1,53 @@
 use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
 #[allow(unused_imports)]
 use near_sdk::{env, near_bindgen};
+use near_sdk::collections::LazyOption;
+use near_sdk::serde::{Deserialize, Serialize};
 
+// A procedural macro written for lesson_5_macro_usage, it writes the get_* and set_* functions for us
+use contract_accessors_derive::ContractAccessors;
 
-near_sdk::setup_alloc!();
+
+mod errors;
+mod ranges;
+mod versions;
+
+pub use errors::Errors;
+pub use ranges::Ranges;
+pub use versions::{
+    ContractV1,
+    StructExampleV1,
+    StructExampleV2,
+    VersionedStructExample,
+};
 
 
 // derive is a macro that generates code for the specifiec traits between ()
 // we use it so the contract gets trait implementations of BorshDeserialize and BorshSerialize
 // StructExample is the state of a contract, so we need these two traits in place
-#[derive(BorshDeserialize, BorshSerialize)]
+// Deserialize and Serialize do the same, but for JSON, so it can be used as argument or return value
+// ContractAccessors writes get_* and set_* for each field marked with #[accessor(...)]
+#[derive(BorshDeserialize, BorshSerialize, ContractAccessors, Debug, Deserialize, PartialEq, Serialize)]
+// serde is re-exported by near_sdk, so we tell the derive macros where to find it
+#[serde(crate = "near_sdk::serde")]
+// The same functions are also written for Contract, calling self.struct_example.get() and self.struct_example.get_mut()
+#[accessors(contract = Contract, field = struct_example, read = get, write = get_mut)]
 // A struct with primitive types
 pub struct StructExample{
+    // The numeric setters check limits, so they are written by hand below
+    #[accessor(get)]
     an_integer: u32,
+    #[accessor(get)]
     another_integer: i32,
+    #[accessor(get)]
     a_floating: f32,
+    // String implements Clone, but not Copy, so get_a_string returns a clone
+    // set_a_string takes ownership of the argument, so no copy is needed
+    #[accessor(get, set, arg = a_string_arg)]
     a_string: String,
+    // Added in the second version, see versions.rs
+    #[accessor(get, set)]
+    a_boolean: bool,
 }
 
 
@@ -33,10 +65,26 @@ impl Default for StructExample{
             another_integer: -1,
             a_floating,
             a_string: String::from("A default string"),
+            a_boolean: false,
         }
     }
 }
 
+// A set of changes to StructExample
+// Each field is optional, only the fields that are Some will be changed
+// Default makes every field None, and serde(default) allows the JSON to leave out any field
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(default)]
+pub struct StructExamplePatch{
+    pub an_integer: Option<u32>,
+    pub another_integer: Option<i32>,
+    pub a_floating: Option<f32>,
+    pub a_string: Option<String>,
+    pub a_boolean: Option<bool>,
+}
+
+
 // Clone creates a copy of the struct
 impl Clone for StructExample{
     // self is an instance of StructExample, and Self (uppercase) is the actual StructExample type.
@@ -45,6 +93,7 @@ impl Clone for StructExample{
         let another_integer: i32 = self.get_another_integer();
         let a_floating: f32 = self.get_a_floating();
         let a_string: String = self.get_a_string();
+        let a_boolean: bool = self.get_a_boolean();
 
         // Self and StructExample are the same here
         Self {
@@ -52,6 +101,7 @@ impl Clone for StructExample{
             another_integer,
             a_floating,
             a_string,
+            a_boolean,
         }
 
     }
@@ -60,49 +110,38 @@ impl Clone for StructExample{
 // Let's implement the functions for StructExample
 impl StructExample{
 
-    // &self means borrowing a StructExample instance, but it can't modify it
-    /// Returns a String copy of the a_string field value
-    pub fn get_a_string(&self) -> String {
-
-        // String implements the Clone trait, but not Copy
-        // Copy creates an instance (a copy) automatically with an instruction like a = b 
-        // Clone creates an instance (a copy) when the code calls clone()
-        let a_string: String = self.a_string.clone();
-
-        return a_string;
-    }
-
-    pub fn get_a_floating(&self) -> f32 {
-        // f32 implements Copy, so this will create a copy of a_floating automatically
-        return self.a_floating;
-    }
-
-    pub fn get_another_integer(&self) -> i32 {
-        // you dont' need to write the 'return' statement every time
-        // if the line is the last one and it doesn't have ';' at the end, assume return
-        self.another_integer
+    // A constructor is just a function that returns Self, "new" is only a naming convention
+    pub fn new(
+        an_integer: u32, 
+        another_integer: i32, 
+        a_floating: f32, 
+        a_string: String, 
+        a_boolean: bool,
+    ) -> Self {
+        // when a variable has the same name as the field, we don't need to write name: value
+        StructExample {
+            an_integer,
+            another_integer,
+            a_floating,
+            a_string,
+            a_boolean,
+        }
     }
 
-    pub fn get_an_integer(&self) -> u32 {
-        self.an_integer
-    }
+    // get_* and set_* for a_string and a_boolean, and every get_*, are written by ContractAccessors.
+    // Run 'cargo expand' to see the generated code.
 
     // '&mut self' will borrow a StructExample instance and also make changes to it
-    pub fn set_a_string(
-        &mut self, 
-        // For the following a_string_arg, the function will take ownership since we are not borrowing it using &
-        // This means this function now owns this piece of memory and 
-        // that code that called this function won't need this string again
-        //
-        // This is one of Rust's superpowers: It allows to minimze the amount of copies we need
-        a_string_arg: String,
-    ) {
-        // as we borrow a mutable self, we can change its field values
-        self.a_string = a_string_arg;
-    }
-
-    pub fn set_a_floating(&mut self, arg: f32) {
+    // NaN and ±Infinity are not equal to themselves and can't be written as JSON, so we don't store them
+    // Result means this function either succeeds with Ok(()) or fails with Err(Errors)
+    pub fn set_a_floating(&mut self, arg: f32) -> Result<(), Errors> {
+        if !arg.is_finite() {
+            return Err(Errors::NotFinite(String::from("a_floating")));
+        }
+
         self.a_floating = arg;
+
+        Ok(())
     }
 
     pub fn set_an_integer(&mut self, an_integer: u32) {
@@ -115,20 +154,49 @@ impl StructExample{
         self.another_integer = another_integer;
     }
 
+    /// Changes all fields that are Some in patch, at once
+    /// 
+    /// If a_floating is not finite, nothing is changed.
+    pub fn update(&mut self, patch: StructExamplePatch) -> Result<(), Errors> {
+        // if let runs the block only if the pattern matches, giving us the value inside Some
+        // Check the only fallible setter first, so either every field changes or none does
+        if let Some(a_floating) = patch.a_floating {
+            self.set_a_floating(a_floating)?;
+        }
+
+        if let Some(an_integer) = patch.an_integer {
+            self.set_an_integer(an_integer);
+        }
+
+        if let Some(another_integer) = patch.another_integer {
+            self.set_another_integer(another_integer);
+        }
+
+        if let Some(a_string) = patch.a_string {
+            self.set_a_string(a_string);
+        }
+
+        if let Some(a_boolean) = patch.a_boolean {
+            self.set_a_boolean(a_boolean);
+        }
+
+        Ok(())
+    }
+
     // if the function doesn't have 'self', then it is a function associated with the type itself
     pub fn just_a_function() {
-        env::log(b"You just called this function");
-        env::log(format!("1 + 1 = {}", 1 + 1).as_bytes());
+        env::log_str("You just called this function");
+        env::log_str(&format!("1 + 1 = {}", 1 + 1));
     }
 
     // if 'self' is an argument, the function will take ownership of the instance
     // this meand the struct will be dropped at the end of the function (unless we return it)
     // this is just an example, you probably won't ever need to do this
     pub fn take_ownership(self) -> u32{
-        env::log(b"Taking ownership of itself");
+        env::log_str("Taking ownership of itself");
 
         let result = format!("an_integer is {}", self.an_integer);
-        env::log(result.as_bytes());
+        env::log_str(&result);
 
         self.an_integer
 
@@ -149,48 +217,104 @@ impl StructExample{
 // For storing data on-chain Borsh is used.
 #[derive(BorshDeserialize, BorshSerialize, Default)]
 pub struct Contract {
-    struct_example: StructExample,
+    // Stored with its version, so StructExample can change without breaking the state
+    struct_example: VersionedStructExample,
+    // The limits chosen by the owner are not a field. 
+    // They are stored under RANGES_KEY, so the layout of Contract stays the same.
 }
 
+// Storage key of the limits for the numeric fields
+const RANGES_KEY: &[u8] = b"ranges";
+
 #[near_bindgen]
 impl Contract{
+    // Limits stored under their own key. Nothing is stored until the owner sets them.
+    fn ranges() -> LazyOption<Ranges> {
+        LazyOption::new(RANGES_KEY, None)
+    }
+
+    fn assert_owner() -> Result<(), Errors> {
+        if env::predecessor_account_id() != env::current_account_id() {
+            return Err(Errors::OwnerOnly);
+        }
+
+        Ok(())
+    }
+
+    // Reads the state written before StructExample was versioned, and converts it to the current layout.
+    // #[private] allows only the contract account to call it.
+    // #[init(ignore_state)] allows an init function to run when there's already a state stored.
+    #[private]
+    #[init(ignore_state)]
+    pub fn migrate() -> Self {
+        let old: ContractV1 = env::state_read().expect("No state to migrate.");
+
+        Contract {
+            struct_example: VersionedStructExample::V1(old.struct_example),
+        }
+    }
+
     // The functions below redirect to the struct functions. 
     // The difference being is that these ones below, represent how we interact with the actual Smart Contract
-    
-    
-    // get_* functions need &self, and set_* need to make changes so we use &mut self
-    // using '&self' for get_* functions
-    pub fn get_a_string(&self) -> String {
-        self.struct_example.get_a_string()
-    }
+    //
+    // ContractAccessors already wrote get_a_string, get_a_floating, get_another_integer, get_an_integer,
+    // get_a_boolean, set_a_string and set_a_boolean in another '#[near_bindgen] impl Contract'.
+    // Only the setters that check the ranges are left here.
 
-    pub fn get_a_floating(&self) -> f32 {
-        self.struct_example.get_a_floating()
+    // #[handle_result] tells near_bindgen to return Ok as JSON, and to panic with the message of Err
+    // '?' returns early with the error, if there is one
+    #[handle_result]
+    pub fn set_a_floating(&mut self, a_floating: f32) -> Result<(), Errors> {
+        self.get_ranges().check_a_floating(a_floating)?;
+        self.struct_example.get_mut().set_a_floating(a_floating)
     }
 
-    pub fn get_another_integer(&self) -> i32 {
-        self.struct_example.get_another_integer()
+    #[handle_result]
+    pub fn set_an_integer(&mut self, an_integer: u32) -> Result<(), Errors> {
+        self.get_ranges().check_an_integer(an_integer)?;
+        self.struct_example.get_mut().set_an_integer(an_integer);
+
+        Ok(())
     }
 
-    pub fn get_an_integer(&self) -> u32 {
-        self.struct_example.get_an_integer()
+    #[handle_result]
+    pub fn set_another_integer(&mut self, another_integer: i32) -> Result<(), Errors> {
+        self.get_ranges().check_another_integer(another_integer)?;
+        self.struct_example.get_mut().set_another_integer(another_integer);
+
+        Ok(())
     }
 
-    // using 'mut &self' for get_* functions
-    pub fn set_a_string(&mut self, a_string_arg: String) {
-        self.struct_example.set_a_string(a_string_arg);
+    // Returns the limits for the numeric fields
+    pub fn get_ranges(&self) -> Ranges {
+        Self::ranges().get().unwrap_or_default()
     }
 
-    pub fn set_a_floating(&mut self, a_floating: f32) {
-        self.struct_example.set_a_floating(a_floating);
+    // Changes the limits for the numeric fields. Values already stored are not checked again.
+    // Only the owner can call this function.
+    // e.g.: set_ranges '{"ranges": {"an_integer": [0, 100], "a_floating": [-1.0, 1.0]}}'
+    #[handle_result]
+    pub fn set_ranges(&mut self, ranges: Ranges) -> Result<(), Errors> {
+        Self::assert_owner()?;
+        ranges.validate()?;
+
+        Self::ranges().set(&ranges);
+
+        Ok(())
     }
 
-    pub fn set_an_integer(&mut self, an_integer: u32) {
-        self.struct_example.set_an_integer(an_integer);
+    // Returns every field at once, as a JSON object
+    pub fn get_all(&self) -> StructExample {
+        self.struct_example.get().into_owned()
     }
 
-    pub fn set_another_integer(&mut self, another_integer: i32) {
-        self.struct_example.set_another_integer(another_integer);
+    // Changes several fields in a single call. Fields left out of the JSON are not changed.
+    // e.g.: update '{"patch": {"an_integer": 10, "a_string": "New string"}}'
+    // If any field is not valid, none of them is changed.
+    #[handle_result]
+    pub fn update(&mut self, patch: StructExamplePatch) -> Result<(), Errors> {
+        self.get_ranges().check_patch(&patch)?;
+        self.struct_example.get_mut().update(patch)
     }
 
     // just_a_function doesn't use '&self', '&mut self' or 'self'
@@ -206,14 +330,14 @@ impl Contract{
         // O compilador não irá nos deixar chamar isso diretamente enquanto o dono for Contract.
         // Most of the issues of ownership can be solved by just cloning the object.
         // A maioria dos problemas de possessão (ownership) podem ser resolvidos apenas clonando objetos
-        let a_copy: StructExample = self.struct_example.clone();
+        let a_copy: StructExample = self.struct_example.get().into_owned();
 
         // a_copy será removido no fim dessa função abaixo.
         let result = a_copy.take_ownership();
 
         // Descomente a linha abaixo e um erro de "move" irá aparecer.
         // Isso é porque chamamos uma função de uma instância que não existe mais.
-        // env::log(format!("Essa linha de código irá gerar um erro {}", a_copy.get_a_floating()).as_bytes());
+        // env::log_str(&format!("Essa linha de código irá gerar um erro {}", a_copy.get_a_floating()));
 
         result
     }
@@ -228,13 +352,16 @@ mod tests{
     use near_sdk::{
         AccountId,
         env,
-        MockedBlockchain,
         testing_env,
         test_utils::VMContextBuilder,
-        json_types::ValidAccountId,
     };
 
     fn env_setup(){
+        env_setup_with_caller("stiltztinkerstein");
+    }
+
+    // Same as env_setup, but the contract is called by the given account
+    fn env_setup_with_caller(predecessor: &str){
         let mut builder: VMContextBuilder = VMContextBuilder::new();
 
         // fields that can be changed via the builder
@@ -253,13 +380,10 @@ mod tests{
         // random_seed
         // is_view
 
-        let account_id: AccountId = String::from("stiltztinkerstein");
+        let account_id: AccountId = "stiltztinkerstein".parse().unwrap();
 
-        builder.current_account_id(
-            ValidAccountId::try_from(
-                account_id.clone()
-            ).unwrap()
-        );
+        builder.current_account_id(account_id.clone());
+        builder.predecessor_account_id(predecessor.parse().unwrap());
 
         testing_env!(builder.build());
 
@@ -347,7 +471,7 @@ mod tests{
 
         let mut contract: Contract = Contract::default();
 
-        contract.set_a_floating(-10.5432);
+        contract.set_a_floating(-10.5432).unwrap();
         
         assert_eq!(
             contract.get_a_floating(),
@@ -361,7 +485,7 @@ mod tests{
 
         let mut contract: Contract = Contract::default();
 
-        contract.set_an_integer(5);
+        contract.set_an_integer(5).unwrap();
 
         assert_eq!(
             contract.get_an_integer(),
@@ -375,7 +499,7 @@ mod tests{
 
         let mut contract: Contract = Contract::default();
 
-        contract.set_another_integer(7);
+        contract.set_another_integer(7).unwrap();
 
         assert_eq!(
             contract.get_another_integer(),
@@ -383,6 +507,179 @@ mod tests{
         );
     }
 
+    #[test]
+    pub fn get_all() {
+        env_setup();
+
+        let contract: Contract = Contract::default();
+
+        assert_eq!(
+            contract.get_all(),
+            StructExample::default(),
+        );
+    }
+
+    #[test]
+    pub fn update() {
+        env_setup();
+
+        let mut contract: Contract = Contract::default();
+
+        // JSON the same way a client would send it, leaving out another_integer and a_floating
+        let patch: StructExamplePatch = near_sdk::serde_json::from_str(
+            r#"{"an_integer": 10, "a_string": "A new string"}"#
+        ).unwrap();
+
+        contract.update(patch).unwrap();
+
+        assert_eq!(contract.get_an_integer(), 10);
+        assert_eq!(contract.get_a_string(), "A new string");
+
+        // Fields left out are not changed
+        assert_eq!(contract.get_another_integer(), -1);
+        assert_eq!(contract.get_a_floating(), 0.5);
+    }
+
+    #[test]
+    pub fn set_a_floating_not_finite() {
+        env_setup();
+
+        let mut contract: Contract = Contract::default();
+
+        for value in [f32::NAN, f32::INFINITY, f32::NEG_INFINITY] {
+            assert!(matches!(
+                contract.set_a_floating(value),
+                Err(Errors::NotFinite(field)) if field == "a_floating"
+            ));
+        }
+
+        // A patch with a non-finite value doesn't change any field
+        let patch = StructExamplePatch { 
+            an_integer: Some(10),
+            a_floating: Some(f32::NAN),
+            ..StructExamplePatch::default()
+        };
+
+        assert!(matches!(contract.update(patch), Err(Errors::NotFinite(_))));
+        assert_eq!(contract.get_all(), StructExample::default());
+    }
+
+    #[test]
+    pub fn ranges() {
+        env_setup();
+
+        let mut contract: Contract = Contract::default();
+
+        contract.set_ranges(Ranges {
+            an_integer: Some((1, 10)),
+            another_integer: Some((-5, 5)),
+            a_floating: Some((0.0, 1.0)),
+        }).unwrap();
+
+        assert!(matches!(contract.set_an_integer(11), Err(Errors::IntegerOutOfRange(_, 11, 1, 10))));
+        assert!(matches!(contract.set_another_integer(-6), Err(Errors::IntegerOutOfRange(_, -6, -5, 5))));
+        assert!(matches!(contract.set_a_floating(1.5), Err(Errors::FloatOutOfRange(..))));
+        assert!(matches!(contract.set_a_floating(f32::NAN), Err(Errors::NotFinite(_))));
+
+        let patch = StructExamplePatch {
+            another_integer: Some(3),
+            an_integer: Some(0),
+            ..StructExamplePatch::default()
+        };
+
+        assert!(matches!(contract.update(patch), Err(Errors::IntegerOutOfRange(..))));
+
+        // Nothing was changed by the rejected calls
+        assert_eq!(contract.get_all(), StructExample::default());
+
+        // Values within range are accepted
+        contract.set_an_integer(10).unwrap();
+        contract.set_a_floating(0.25).unwrap();
+    }
+
+    #[test]
+    pub fn set_ranges_invalid() {
+        env_setup();
+
+        let mut contract: Contract = Contract::default();
+
+        let reversed = Ranges { an_integer: Some((10, 1)), ..Ranges::default() };
+        assert!(matches!(contract.set_ranges(reversed), Err(Errors::InvalidRange(_))));
+
+        let infinite = Ranges { a_floating: Some((0.0, f32::INFINITY)), ..Ranges::default() };
+        assert!(matches!(contract.set_ranges(infinite), Err(Errors::InvalidRange(_))));
+
+        env_setup_with_caller("alice");
+
+        assert!(matches!(contract.set_ranges(Ranges::default()), Err(Errors::OwnerOnly)));
+    }
+
+    #[test]
+    pub fn migrate() {
+        env_setup();
+
+        let old_example = StructExampleV1 {
+            an_integer: 3,
+            another_integer: -3,
+            a_floating: 1.5,
+            a_string: String::from("Stored by the first version"),
+        };
+
+        // The state as the first release wrote it, field after field, without a version or ranges.
+        let mut bytes: Vec<u8> = Vec::new();
+        bytes.extend(3u32.to_le_bytes());
+        bytes.extend((-3i32).to_le_bytes());
+        bytes.extend(1.5f32.to_le_bytes());
+        bytes.extend((old_example.a_string.len() as u32).to_le_bytes());
+        bytes.extend(old_example.a_string.as_bytes());
+
+        // ContractV1 reads the same bytes
+        assert_eq!(ContractV1 { struct_example: old_example }.try_to_vec().unwrap(), bytes);
+
+        env::storage_write(b"STATE", &bytes);
+
+        let mut contract: Contract = Contract::migrate();
+
+        assert_eq!(contract.get_an_integer(), 3);
+        assert_eq!(contract.get_another_integer(), -3);
+        assert_eq!(contract.get_a_floating(), 1.5);
+        assert_eq!(contract.get_a_string(), "Stored by the first version");
+        assert!(!contract.get_a_boolean());
+        assert_eq!(contract.get_ranges(), Ranges::default());
+
+        // The migrated state can be written and read back
+        contract.set_a_boolean(true);
+        env::state_write(&contract);
+
+        let loaded: Contract = env::state_read().unwrap();
+
+        assert!(loaded.get_a_boolean());
+        assert_eq!(loaded.get_a_string(), "Stored by the first version");
+    }
+
+    #[test]
+    pub fn versioned_struct_example() {
+        let old_example = StructExampleV1 {
+            an_integer: 3,
+            another_integer: -3,
+            a_floating: 1.5,
+            a_string: String::from("Stored by the first version"),
+        };
+
+        // V1 bytes still load as VersionedStructExample
+        let bytes: Vec<u8> = VersionedStructExample::V1(old_example).try_to_vec().unwrap();
+        let mut versioned = VersionedStructExample::try_from_slice(&bytes).unwrap();
+
+        assert_eq!(versioned.get().get_an_integer(), 3);
+        assert!(matches!(versioned, VersionedStructExample::V1(_)));
+
+        // Changing it upgrades to the latest version
+        versioned.get_mut().set_a_boolean(true);
+
+        assert!(matches!(versioned, VersionedStructExample::V2(_)));
+        assert!(versioned.get().get_a_boolean());
+    }
+
     #[test]
     pub fn just_a_function(){
         env_setup();
@@ -0,0 +1,106 @@
+use near_sdk::{
+    borsh::{ self, BorshDeserialize, BorshSerialize },
+    serde::{ Deserialize, Serialize },
+};
+
+use crate::{
+    Errors,
+    StructExamplePatch,
+};
+
+
+/// Optional (minimum, maximum) limits for the numeric fields of StructExample.
+/// 
+/// None means the field accepts any value of its type.
+#[derive(BorshDeserialize, BorshSerialize, Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(default)]
+pub struct Ranges {
+    pub an_integer: Option<(u32, u32)>,
+    pub another_integer: Option<(i32, i32)>,
+    pub a_floating: Option<(f32, f32)>,
+}
+
+
+impl Ranges {
+    /// Makes sure each range has min <= max, and the float range is finite.
+    pub fn validate(&self) -> Result<(), Errors> {
+        if let Some((min, max)) = self.an_integer {
+            if min > max {
+                return Err(Errors::InvalidRange(String::from("an_integer")));
+            }
+        }
+
+        if let Some((min, max)) = self.another_integer {
+            if min > max {
+                return Err(Errors::InvalidRange(String::from("another_integer")));
+            }
+        }
+
+        if let Some((min, max)) = self.a_floating {
+            if !min.is_finite() || !max.is_finite() || min > max {
+                return Err(Errors::InvalidRange(String::from("a_floating")));
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn check_an_integer(&self, value: u32) -> Result<(), Errors> {
+        match self.an_integer {
+            Some((min, max)) if value < min || value > max => Err(Errors::IntegerOutOfRange(
+                String::from("an_integer"), 
+                i64::from(value), 
+                i64::from(min), 
+                i64::from(max),
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    pub fn check_another_integer(&self, value: i32) -> Result<(), Errors> {
+        match self.another_integer {
+            Some((min, max)) if value < min || value > max => Err(Errors::IntegerOutOfRange(
+                String::from("another_integer"), 
+                i64::from(value), 
+                i64::from(min), 
+                i64::from(max),
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// Also rejects NaN and ±Infinity, even when there's no range.
+    pub fn check_a_floating(&self, value: f32) -> Result<(), Errors> {
+        if !value.is_finite() {
+            return Err(Errors::NotFinite(String::from("a_floating")));
+        }
+
+        match self.a_floating {
+            Some((min, max)) if value < min || value > max => Err(Errors::FloatOutOfRange(
+                String::from("a_floating"), 
+                value, 
+                min, 
+                max,
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// Checks every field that would be changed by patch.
+    pub fn check_patch(&self, patch: &StructExamplePatch) -> Result<(), Errors> {
+        if let Some(an_integer) = patch.an_integer {
+            self.check_an_integer(an_integer)?;
+        }
+
+        if let Some(another_integer) = patch.another_integer {
+            self.check_another_integer(another_integer)?;
+        }
+
+        if let Some(a_floating) = patch.a_floating {
+            self.check_a_floating(a_floating)?;
+        }
+
+        Ok(())
+    }
+}
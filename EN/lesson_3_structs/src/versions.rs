@@ -0,0 +1,99 @@
+use std::borrow::Cow;
+
+use near_sdk::borsh::{ self, BorshDeserialize, BorshSerialize };
+
+use crate::StructExample;
+
+
+/// First layout of StructExample, before a_boolean was added.
+#[derive(BorshDeserialize, BorshSerialize, Clone, Debug, PartialEq)]
+pub struct StructExampleV1 {
+    pub an_integer: u32,
+    pub another_integer: i32,
+    pub a_floating: f32,
+    pub a_string: String,
+}
+
+/// Current layout.
+pub type StructExampleV2 = StructExample;
+
+
+/// StructExample stored with its version.
+/// 
+/// Borsh writes the variant index before the value, so a state written with an older version can still be read.
+/// To change StructExample, add a new variant and an upgrade path in From. 
+/// Never change or reorder the existing variants.
+#[derive(BorshDeserialize, BorshSerialize, Clone, Debug, PartialEq)]
+pub enum VersionedStructExample {
+    V1(StructExampleV1),
+    V2(StructExampleV2),
+}
+
+
+impl From<StructExampleV1> for StructExampleV2 {
+    fn from(old: StructExampleV1) -> Self {
+        StructExample::new(
+            old.an_integer,
+            old.another_integer,
+            old.a_floating,
+            old.a_string,
+            false,
+        )
+    }
+}
+
+impl From<VersionedStructExample> for StructExample {
+    fn from(versioned: VersionedStructExample) -> Self {
+        match versioned {
+            VersionedStructExample::V1(old) => StructExample::from(old),
+            VersionedStructExample::V2(current) => current,
+        }
+    }
+}
+
+impl From<StructExample> for VersionedStructExample {
+    fn from(current: StructExample) -> Self {
+        VersionedStructExample::V2(current)
+    }
+}
+
+impl Default for VersionedStructExample {
+    fn default() -> Self {
+        VersionedStructExample::from(StructExample::default())
+    }
+}
+
+
+impl VersionedStructExample {
+    /// Returns the latest version. Older versions are converted from a copy, self doesn't change.
+    pub fn get(&self) -> Cow<'_, StructExample> {
+        match self {
+            VersionedStructExample::V2(current) => Cow::Borrowed(current),
+            outdated => Cow::Owned(StructExample::from(outdated.clone())),
+        }
+    }
+
+    /// Upgrades self to the latest version and returns it, so it can be changed.
+    pub fn get_mut(&mut self) -> &mut StructExample {
+        if let VersionedStructExample::V1(old) = self {
+            *self = VersionedStructExample::V2(StructExample::from(old.clone()));
+        }
+
+        match self {
+            VersionedStructExample::V2(current) => current,
+            _ => unreachable!("StructExample was upgraded above"),
+        }
+    }
+}
+
+
+/// Contract state of the first release, before StructExample was versioned.
+/// 
+/// It must match the layout that is deployed: `Contract { struct_example: StructExample }`, 
+/// with the four fields of StructExampleV1 and nothing else.
+/// 
+/// Only used by migrate, to read the old state.
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct ContractV1 {
+    pub struct_example: StructExampleV1,
+}
@@ -3,7 +3,32 @@
 //! or create a .rs file with the same filename as the module you want
 pub mod specific_module;
 
+use crate::{
+    greeter::Greeter,
+    locale::Locale,
+};
 
-pub fn hello() -> String {
-    String::from("Hello from crate::a_module")
+
+pub fn hello(locale: &Locale) -> String {
+    let greeting: &str = match locale {
+        Locale::En => "Hello from crate::a_module",
+        Locale::Es => "Hola desde crate::a_module",
+        Locale::PtBr => "Olá de crate::a_module",
+    };
+
+    String::from(greeting)
+}
+
+
+/// Greeter for this module. A struct without fields is enough to implement a trait.
+pub struct ModuleGreeter;
+
+impl Greeter for ModuleGreeter {
+    fn path(&self) -> &'static str {
+        "a_module"
+    }
+
+    fn greet(&self, locale: &Locale) -> String {
+        hello(locale)
+    }
 }
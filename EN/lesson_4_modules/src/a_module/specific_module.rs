@@ -1,7 +1,35 @@
+use crate::{
+    greeter::Greeter,
+    locale::Locale,
+    Errors,
+};
+
+
 // Specifying (crate) after pub allows a function (or module, trait, etc) to be available to 
 // other local modules (the same crate), but not to external crates
-pub(crate) fn hello() -> String{
-    String::from("Hello from crate::a_module::specific_module")
+pub(crate) fn hello(locale: &Locale) -> String{
+    let greeting: &str = match locale {
+        Locale::En => "Hello from crate::a_module::specific_module",
+        Locale::Es => "Hola desde crate::a_module::specific_module",
+        Locale::PtBr => "Olá de crate::a_module::specific_module",
+    };
+
+    String::from(greeting)
+}
+
+
+// hello is private to this crate, but the greeter is public. 
+// So external crates can greet through the trait, without calling hello directly.
+pub struct ModuleGreeter;
+
+impl Greeter for ModuleGreeter {
+    fn path(&self) -> &'static str {
+        "a_module::specific_module"
+    }
+
+    fn greet(&self, locale: &Locale) -> String {
+        hello(locale)
+    }
 }
 
 /// use the panic! macro to raise an error and halt/terminate the application
@@ -10,6 +38,12 @@ pub fn this_will_panic() {
     panic!("A panic has just happened");
 }
 
+/// the same as above, but the caller decides what to do with the error
+/// Result is either Ok(value) or Err(error), so the compiler makes sure the error is handled
+pub fn this_will_fail() -> Result<(), Errors> {
+    Err(Errors::AFailureHappened)
+}
+
 
 // cfg(test) tells the compiler these is a module with tests (test harness)
 // and you can then use 'cargo test'
@@ -24,9 +58,14 @@ mod tests{
         // we'll try to import it from the test directory, which would cause an error
         // the test directory is its own crate
         assert_eq!(
-            hello(),
+            hello(&Locale::En),
             "Hello from crate::a_module::specific_module",
         );
+
+        assert_eq!(
+            hello(&Locale::PtBr),
+            "Olá de crate::a_module::specific_module",
+        );
     }
 
 
@@ -37,4 +76,14 @@ mod tests{
     fn this_will_panic_test() {
         this_will_panic();
     }
+
+
+    #[test]
+    // with Result, there's no panic to expect. We just compare the error
+    fn this_will_fail_test() {
+        assert_eq!(
+            this_will_fail(),
+            Err(Errors::AFailureHappened),
+        );
+    }
 }
\ No newline at end of file
@@ -1,5 +1,30 @@
-pub fn hello() -> String {
-    String::from("Hello from another_module")
+use crate::{
+    greeter::Greeter,
+    locale::Locale,
+};
+
+
+pub fn hello(locale: &Locale) -> String {
+    let greeting: &str = match locale {
+        Locale::En => "Hello from another_module",
+        Locale::Es => "Hola desde another_module",
+        Locale::PtBr => "Olá de another_module",
+    };
+
+    String::from(greeting)
+}
+
+
+pub struct ModuleGreeter;
+
+impl Greeter for ModuleGreeter {
+    fn path(&self) -> &'static str {
+        "another_module"
+    }
+
+    fn greet(&self, locale: &Locale) -> String {
+        hello(locale)
+    }
 }
 
 
@@ -0,0 +1,29 @@
+use near_sdk::{
+    borsh::{self, BorshSerialize},
+    FunctionError,
+};
+
+
+/// Errors returned by the contract functions.
+/// 
+/// Functions marked with #[handle_result] return Ok as JSON. 
+/// An Err makes the call fail with the message from Display, without having to call panic! ourselves.
+#[derive(BorshSerialize, Debug, FunctionError, PartialEq)]
+pub enum Errors {
+    UnknownGreeter(String),
+    InvalidLocale(String),
+    OwnerOnly,
+    AFailureHappened,
+}
+
+
+impl std::fmt::Display for Errors {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Errors::UnknownGreeter(path) => write!(f, "No greeter found for module path {}.", path),
+            Errors::InvalidLocale(locale) => write!(f, "Invalid locale {}. Use one of: en, es, pt-br.", locale),
+            Errors::OwnerOnly => write!(f, "Only the owner of the contract can do this."),
+            Errors::AFailureHappened => write!(f, "A failure has just happened"),
+        }
+    }
+}
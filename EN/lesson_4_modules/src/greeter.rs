@@ -0,0 +1,73 @@
+//! A trait implemented by every module that can greet, and a registry to find them by path.
+//! 
+//! To add a greeter, implement Greeter for a type in the new module and register it in Registry::default.
+//! The contract function greet will find it without any new contract function.
+
+use std::collections::BTreeMap;
+
+use crate::{
+    a_module,
+    another_module,
+    yet_another_module,
+    Errors,
+    locale::Locale,
+};
+
+
+/// Something that returns a greeting in the given language.
+pub trait Greeter {
+    /// Path used to find this greeter, like "a_module::specific_module".
+    fn path(&self) -> &'static str;
+
+    /// Returns the greeting in the given language.
+    fn greet(&self, locale: &Locale) -> String;
+}
+
+
+/// Every greeter, mapped by path.
+/// 
+/// Box<dyn Greeter> allows storing different types in the same map, as long as they implement Greeter.
+pub struct Registry {
+    greeters: BTreeMap<&'static str, Box<dyn Greeter>>,
+}
+
+
+impl Default for Registry {
+    fn default() -> Self {
+        let mut registry = Registry {
+            greeters: BTreeMap::new(),
+        };
+
+        registry.register(Box::new(a_module::ModuleGreeter));
+        registry.register(Box::new(a_module::specific_module::ModuleGreeter));
+        registry.register(Box::new(another_module::ModuleGreeter));
+        registry.register(Box::new(yet_another_module::ModuleGreeter));
+        registry.register(Box::new(yet_another_module::DeepModuleGreeter));
+
+        registry
+    }
+}
+
+
+impl Registry {
+    /// Adds a greeter. Replaces any greeter with the same path.
+    pub fn register(&mut self, greeter: Box<dyn Greeter>) {
+        self.greeters.insert(greeter.path(), greeter);
+    }
+
+    /// Returns the greeter with the given path.
+    pub fn get(&self, path: &str) -> Result<&dyn Greeter, Errors> {
+        match self.greeters.get(path) {
+            Some(greeter) => Ok(greeter.as_ref()),
+            None => Err(Errors::UnknownGreeter(String::from(path))),
+        }
+    }
+
+    /// Returns the path of every greeter, in alphabetical order.
+    pub fn paths(&self) -> Vec<String> {
+        self.greeters
+            .keys()
+            .map(|path| String::from(*path))
+            .collect()
+    }
+}
@@ -25,9 +25,21 @@ mod another_module;
 // and one more file based module
 mod yet_another_module;
 
+// the language used by every hello function
+pub mod locale;
+
+// the Greeter trait, implemented by every module above, and the registry that finds them
+pub mod greeter;
+
+mod errors;
+
 use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::collections::{LookupMap, Vector};
 #[allow(unused_imports)]
-use near_sdk::{env, near_bindgen};
+use near_sdk::{env, near_bindgen, AccountId, BorshStorageKey};
+
+// a crate from outside this lesson, shared with the other lessons through a path dependency
+use contract_log::contract_log;
 
 // we can use the 'as' keyword to alias functions
 use a_module::hello as hello;
@@ -47,70 +59,229 @@ use yet_another_module::a_deep_function as hello4;
 // use another_module::this_is_private;
 
 pub use a_module::specific_module::this_will_panic;
+pub use a_module::specific_module::this_will_fail;
 
+pub use errors::Errors;
 
-near_sdk::setup_alloc!();
+use greeter::Registry;
+use locale::Locale;
 
 
-// this function will help to keep cleaner code
-pub fn log(message: &str) {
-    env::log(message.as_bytes());
+/// Each collection needs a unique prefix in storage, so we use this enum as key.
+#[derive(BorshStorageKey, BorshSerialize)]
+enum StorageKey {
+    Phrases,
+    // Each account has its own Vector, so the account id is part of the key
+    PhraseHistory(AccountId),
 }
 
 
 #[near_bindgen]
-#[derive(Clone, Default, BorshDeserialize, BorshSerialize)]
+#[derive(BorshDeserialize, BorshSerialize)]
 pub struct Contract {
-    stored_phrase: String,
+    // every phrase stored by each account, oldest first
+    phrases: LookupMap<AccountId, Vector<String>>,
+    locale: Locale,
+}
+
+
+impl Default for Contract {
+    fn default() -> Self {
+        Contract {
+            phrases: LookupMap::new(StorageKey::Phrases),
+            locale: Locale::default(),
+        }
+    }
+}
+
+
+impl Contract {
+    // Appends phrase to the history of the caller, and logs it
+    fn store_phrase(&mut self, phrase: String) {
+        let account_id: AccountId = env::predecessor_account_id();
+
+        let mut history: Vector<String> = match self.phrases.get(&account_id) {
+            Some(history) => history,
+            None => Vector::new(StorageKey::PhraseHistory(account_id.clone())),
+        };
+
+        history.push(&phrase);
+        // The Vector keeps its length in the map, so we insert it again after each push
+        self.phrases.insert(&account_id, &history);
+
+        contract_log!(info, "{}", phrase);
+    }
+
+    // Converts a locale code into Locale
+    fn parse_locale(locale: String) -> Result<Locale, Errors> {
+        Locale::new(&locale).ok_or(Errors::InvalidLocale(locale))
+    }
+
+    // Stores the phrase returned by hello in the given locale, or in the contract locale if None
+    fn try_store_phrase(&mut self, hello: fn(&Locale) -> String, locale: Option<String>) -> Result<String, Errors> {
+        let locale: Locale = match locale {
+            None => self.locale,
+            Some(locale) => Self::parse_locale(locale)?,
+        };
+
+        let phrase: String = hello(&locale);
+        self.store_phrase(phrase.clone());
+
+        Ok(phrase)
+    }
 }
 
 
 #[near_bindgen]
 impl Contract{
-    /// Returns the stored string value
+    /// Returns the last phrase stored by the caller. Empty if there's none.
     pub fn get_phrase(&self) -> String {
-        self.stored_phrase.clone()
+        self.get_phrase_for(env::predecessor_account_id())
+    }
+
+    /// Returns the last phrase stored by the given account. Empty if there's none.
+    pub fn get_phrase_for(&self, account_id: AccountId) -> String {
+        self.phrases
+            .get(&account_id)
+            .and_then(|history| history.get(history.len().checked_sub(1)?))
+            .unwrap_or_default()
+    }
+
+    /// Returns up to limit phrases stored by the given account, oldest first, starting at index from.
+    pub fn get_phrase_history(&self, account_id: AccountId, from: u64, limit: u64) -> Vec<String> {
+        let history: Vector<String> = match self.phrases.get(&account_id) {
+            Some(history) => history,
+            None => return vec![],
+        };
+
+        (from..history.len().min(from.saturating_add(limit)))
+            .filter_map(|index| history.get(index))
+            .collect()
+    }
+
+    /// Returns the code of the language used by the hello functions
+    pub fn get_locale(&self) -> String {
+        self.locale.to_string()
+    }
+
+    /// Changes the language used by the hello functions, for every caller.
+    /// 
+    /// Only the owner (the contract account) can call it. 
+    /// Other accounts can use the try_hello functions to pick a language for a single call.
+    /// 
+    /// locale: "en" | "es" | "pt-br"
+    /// 
+    /// # Errors
+    ///  - OwnerOnly: If the caller is not the contract account.
+    ///  - InvalidLocale: If locale is not one of the above.
+    #[handle_result]
+    pub fn set_locale(&mut self, locale: String) -> Result<(), Errors> {
+        if env::predecessor_account_id() != env::current_account_id() {
+            return Err(Errors::OwnerOnly);
+        }
+
+        self.locale = Self::parse_locale(locale)?;
+
+        Ok(())
     }
 
     /// Logs "Hello from crate::a_module" and 
-    /// stores that string value 
+    /// stores that string value in the history of the caller
+    /// 
+    /// Like the other hello functions, it uses the language selected with set_locale
     pub fn hello(&mut self) {        
-        self.stored_phrase = hello();
-        log(&hello());
+        self.store_phrase(hello(&self.locale));
     }
 
     /// Logs "Hello from crate::a_module::specific_module" and 
-    /// stores that string value 
+    /// stores that string value in the history of the caller
     pub fn hello1(&mut self) {
-        self.stored_phrase = hello1();
-        log(&hello1());
+        self.store_phrase(hello1(&self.locale));
     }
 
     /// Logs "Hello from another module" and 
-    /// stores that string value 
+    /// stores that string value in the history of the caller
     pub fn hello2(&mut self) {
-        self.stored_phrase = hello2();
-        log(&hello2());
+        self.store_phrase(hello2(&self.locale));
     }
 
     /// Logs "Hello from yet_another_module" and 
-    /// stores that string value 
+    /// stores that string value in the history of the caller
     pub fn hello3(&mut self) {
-        self.stored_phrase = hello3();
-        log(&hello3());
+        self.store_phrase(hello3(&self.locale));
     }
 
     /// Logs "Called a deep function" and 
-    /// stores that string value 
+    /// stores that string value in the history of the caller
     pub fn hello4(&mut self) {
-        self.stored_phrase = hello4();
-        log(&hello4());
+        self.store_phrase(hello4(&self.locale));
+    }
+
+    // The try_hello functions below are fallible versions of the hello functions. 
+    // They return the phrase and can use another language, just for this call.
+    // If the language is invalid, they return Errors::InvalidLocale and nothing is stored.
+
+    /// Same as hello, but in the given locale, if any
+    #[handle_result]
+    pub fn try_hello(&mut self, locale: Option<String>) -> Result<String, Errors> {
+        self.try_store_phrase(hello, locale)
+    }
+
+    /// Same as hello1, but in the given locale, if any
+    #[handle_result]
+    pub fn try_hello1(&mut self, locale: Option<String>) -> Result<String, Errors> {
+        self.try_store_phrase(hello1, locale)
+    }
+
+    /// Same as hello2, but in the given locale, if any
+    #[handle_result]
+    pub fn try_hello2(&mut self, locale: Option<String>) -> Result<String, Errors> {
+        self.try_store_phrase(hello2, locale)
+    }
+
+    /// Same as hello3, but in the given locale, if any
+    #[handle_result]
+    pub fn try_hello3(&mut self, locale: Option<String>) -> Result<String, Errors> {
+        self.try_store_phrase(hello3, locale)
+    }
+
+    /// Same as hello4, but in the given locale, if any
+    #[handle_result]
+    pub fn try_hello4(&mut self, locale: Option<String>) -> Result<String, Errors> {
+        self.try_store_phrase(hello4, locale)
+    }
+
+    /// Stores and returns the greeting of the module at module_path, 
+    /// like "a_module::specific_module". 
+    /// 
+    /// Returns Errors::UnknownGreeter if there's no greeter with that path. See list_greeters.
+    #[handle_result]
+    pub fn greet(&mut self, module_path: String) -> Result<String, Errors> {
+        let greeting: String = Registry::default()
+            .get(&module_path)?
+            .greet(&self.locale);
+
+        self.store_phrase(greeting.clone());
+
+        Ok(greeting)
+    }
+
+    /// Returns the path of every module that can be used with greet
+    pub fn list_greeters(&self) -> Vec<String> {
+        Registry::default().paths()
     }
 
     /// this function will panic when called, with a message "A panic just happened"
     pub fn this_will_panic() {    
         this_will_panic();
     }
+
+    /// this function will always return Errors::AFailureHappened.
+    /// For a client, the call fails just like the one above, with the message "A failure has just happened"
+    #[handle_result]
+    pub fn this_will_fail() -> Result<(), Errors> {
+        this_will_fail()
+    }
 }
 
 // Tests can be found in the 'tests' directory. This best practice helps with keeping our code organized. 
@@ -0,0 +1,45 @@
+//! Languages the greetings can be returned in.
+//! 
+//! Each module matches on Locale to pick its own greeting, 
+//! so translations stay next to the function that returns them.
+
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+
+
+/// A language supported by the contract. Default is English.
+#[derive(BorshDeserialize, BorshSerialize, Clone, Copy, Debug, Default, PartialEq)]
+pub enum Locale {
+    #[default]
+    En,
+    Es,
+    PtBr,
+}
+
+
+impl Locale {
+    /// Every locale, in the order they are listed to users.
+    pub const ALL: [Locale; 3] = [Locale::En, Locale::Es, Locale::PtBr];
+
+    /// Returns the locale for a code like "en", "es" or "pt-br". Case doesn't matter.
+    pub fn new(code: &str) -> Option<Locale> {
+        Locale::ALL
+            .into_iter()
+            .find(|locale| locale.code().eq_ignore_ascii_case(code))
+    }
+
+    /// Returns the code used to select this locale.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Locale::En => "en",
+            Locale::Es => "es",
+            Locale::PtBr => "pt-br",
+        }
+    }
+}
+
+
+impl std::fmt::Display for Locale {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.code())
+    }
+}
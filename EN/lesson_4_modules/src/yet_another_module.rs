@@ -5,6 +5,33 @@ mod internal_module;
 // we export a_deep_function as public so other modules can access it
 pub use internal_module::a_deep_module::a_deep_function;
 
-pub fn hello() -> String {
-    String::from("Hello from yet_another_module")
+// the same goes for its greeter, renamed so it doesn't clash with the one below
+pub use internal_module::a_deep_module::ModuleGreeter as DeepModuleGreeter;
+
+use crate::{
+    greeter::Greeter,
+    locale::Locale,
+};
+
+pub fn hello(locale: &Locale) -> String {
+    let greeting: &str = match locale {
+        Locale::En => "Hello from yet_another_module",
+        Locale::Es => "Hola desde yet_another_module",
+        Locale::PtBr => "Olá de yet_another_module",
+    };
+
+    String::from(greeting)
+}
+
+
+pub struct ModuleGreeter;
+
+impl Greeter for ModuleGreeter {
+    fn path(&self) -> &'static str {
+        "yet_another_module"
+    }
+
+    fn greet(&self, locale: &Locale) -> String {
+        hello(locale)
+    }
 }
@@ -1,4 +1,29 @@
+use crate::{
+    greeter::Greeter,
+    locale::Locale,
+};
+
 /// a public function found in a_deep_module
-pub fn a_deep_function() -> String {
-    String::from("Called a deep function")
+pub fn a_deep_function(locale: &Locale) -> String {
+    let greeting: &str = match locale {
+        Locale::En => "Called a deep function",
+        Locale::Es => "Se llamó a una función profunda",
+        Locale::PtBr => "Chamou uma função profunda",
+    };
+
+    String::from(greeting)
+}
+
+
+/// The path is where the module really is, even though the module can't be reached from outside.
+pub struct ModuleGreeter;
+
+impl Greeter for ModuleGreeter {
+    fn path(&self) -> &'static str {
+        "yet_another_module::internal_module::a_deep_module"
+    }
+
+    fn greet(&self, locale: &Locale) -> String {
+        a_deep_function(locale)
+    }
 }
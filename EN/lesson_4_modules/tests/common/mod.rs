@@ -1,13 +1,16 @@
 use near_sdk::{
     AccountId,
     env,
-    MockedBlockchain,
     testing_env,
     test_utils::VMContextBuilder,
-    json_types::ValidAccountId,
 };
 
 pub fn env_setup(){
+    env_setup_with_caller("stiltztinkerstein");
+}
+
+/// Same as env_setup, but the contract is called by the given account
+pub fn env_setup_with_caller(predecessor: &str){
     let mut builder: VMContextBuilder = VMContextBuilder::new();
 
     // fields than can be set with the builder
@@ -26,13 +29,10 @@ pub fn env_setup(){
     // random_seed
     // is_view
 
-    let account_id: AccountId = String::from("stiltztinkerstein");
+    let account_id: AccountId = "stiltztinkerstein".parse().unwrap();
 
-    builder.current_account_id(
-        ValidAccountId::try_from(
-            account_id.clone()
-        ).unwrap()
-    );
+    builder.current_account_id(account_id.clone());
+    builder.predecessor_account_id(predecessor.parse().unwrap());
 
     testing_env!(builder.build());
 
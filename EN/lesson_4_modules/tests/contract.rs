@@ -6,9 +6,15 @@ mod common;
 // The 'test' directory is its own crate, 
 // So when we import our own modules we need to refer to them as
 // "lesson_4_modules::" instead of "crates::"
-use lesson_4_modules::Contract;
+use lesson_4_modules::{
+    Contract,
+    Errors,
+};
 
-use common::env_setup;
+use common::{
+    env_setup,
+    env_setup_with_caller,
+};
 
 
 #[test]
@@ -17,8 +23,8 @@ pub fn get_phrase() {
 
     let contract: Contract = Contract::default();
 
-    // we used derive(Default) in our contract
-    // and the default for String is an empty string
+    // phrases are stored per caller, and this caller hasn't stored any yet,
+    // so get_phrase returns an empty string
     assert_eq!(contract.get_phrase(), "");
 }
 
@@ -102,6 +108,219 @@ pub fn hello4() {
 }
 
 
+/// Each account has its own phrases
+#[test]
+pub fn phrases_per_account() {
+    env_setup_with_caller("alice");
+
+    let mut contract: Contract = Contract::default();
+
+    contract.hello();
+    contract.hello1();
+
+    env_setup_with_caller("bob");
+
+    contract.hello2();
+
+    assert_eq!(contract.get_phrase(), "Hello from another_module");
+    assert_eq!(
+        contract.get_phrase_for("alice".parse().unwrap()),
+        "Hello from crate::a_module::specific_module",
+    );
+    assert_eq!(contract.get_phrase_for("carol".parse().unwrap()), "");
+}
+
+
+/// History is returned oldest first, from the given index
+#[test]
+pub fn phrase_history() {
+    env_setup_with_caller("alice");
+
+    let mut contract: Contract = Contract::default();
+
+    contract.hello();
+    contract.hello3();
+    contract.hello4();
+
+    env_setup_with_caller("bob");
+
+    contract.hello2();
+
+    assert_eq!(
+        contract.get_phrase_history("alice".parse().unwrap(), 0, 10),
+        vec![
+            "Hello from crate::a_module",
+            "Hello from yet_another_module",
+            "Called a deep function",
+        ],
+    );
+
+    assert_eq!(
+        contract.get_phrase_history("alice".parse().unwrap(), 1, 1),
+        vec!["Hello from yet_another_module"],
+    );
+
+    assert_eq!(
+        contract.get_phrase_history("bob".parse().unwrap(), 0, 10),
+        vec!["Hello from another_module"],
+    );
+
+    assert!(contract.get_phrase_history("carol".parse().unwrap(), 0, 10).is_empty());
+}
+
+
+/// greet finds the module by path, and stores its greeting like the hello functions
+#[test]
+pub fn greet() {
+    env_setup();
+
+    let mut contract: Contract = Contract::default();
+
+    assert_eq!(
+        contract.greet(String::from("a_module::specific_module")),
+        Ok(String::from("Hello from crate::a_module::specific_module")),
+    );
+
+    assert_eq!(contract.get_phrase(), "Hello from crate::a_module::specific_module");
+
+    contract.set_locale(String::from("es")).unwrap();
+
+    assert_eq!(
+        contract.greet(String::from("yet_another_module::internal_module::a_deep_module")),
+        Ok(String::from("Se llamó a una función profunda")),
+    );
+}
+
+
+#[test]
+pub fn greet_unknown_module() {
+    env_setup();
+
+    let mut contract: Contract = Contract::default();
+
+    assert_eq!(
+        contract.greet(String::from("a_module::unknown")),
+        Err(Errors::UnknownGreeter(String::from("a_module::unknown"))),
+    );
+
+    // Nothing was stored
+    assert_eq!(contract.get_phrase(), "");
+}
+
+
+#[test]
+pub fn list_greeters() {
+    env_setup();
+
+    let contract: Contract = Contract::default();
+
+    assert_eq!(
+        contract.list_greeters(),
+        vec![
+            "a_module",
+            "a_module::specific_module",
+            "another_module",
+            "yet_another_module",
+            "yet_another_module::internal_module::a_deep_module",
+        ],
+    );
+}
+
+
+/// The same functions return their greeting in the selected language
+#[test]
+pub fn set_locale() {
+    env_setup();
+
+    let mut contract: Contract = Contract::default();
+
+    assert_eq!(contract.get_locale(), "en");
+
+    contract.set_locale(String::from("es")).unwrap();
+    contract.hello2();
+
+    assert_eq!(contract.get_phrase(), "Hola desde another_module");
+
+    // case doesn't matter
+    contract.set_locale(String::from("PT-BR")).unwrap();
+    contract.hello4();
+
+    assert_eq!(contract.get_locale(), "pt-br");
+    assert_eq!(contract.get_phrase(), "Chamou uma função profunda");
+}
+
+
+#[test]
+pub fn set_locale_invalid() {
+    env_setup();
+
+    let mut contract: Contract = Contract::default();
+
+    assert_eq!(
+        contract.set_locale(String::from("fr")),
+        Err(Errors::InvalidLocale(String::from("fr"))),
+    );
+
+    // locale didn't change
+    assert_eq!(contract.get_locale(), "en");
+}
+
+
+#[test]
+pub fn set_locale_owner_only() {
+    env_setup_with_caller("alice");
+
+    let mut contract: Contract = Contract::default();
+
+    assert_eq!(
+        contract.set_locale(String::from("es")),
+        Err(Errors::OwnerOnly),
+    );
+
+    assert_eq!(contract.get_locale(), "en");
+}
+
+
+/// try_hello functions return the phrase, in the given locale or in the contract locale
+#[test]
+pub fn try_hello() {
+    env_setup();
+
+    let mut contract: Contract = Contract::default();
+
+    assert_eq!(
+        contract.try_hello(None),
+        Ok(String::from("Hello from crate::a_module")),
+    );
+
+    assert_eq!(
+        contract.try_hello3(Some(String::from("es"))),
+        Ok(String::from("Hola desde yet_another_module")),
+    );
+
+    assert_eq!(contract.get_phrase(), "Hola desde yet_another_module");
+
+    // the contract locale is not changed
+    assert_eq!(contract.get_locale(), "en");
+}
+
+
+#[test]
+pub fn try_hello_invalid_locale() {
+    env_setup();
+
+    let mut contract: Contract = Contract::default();
+
+    assert_eq!(
+        contract.try_hello1(Some(String::from("xx"))),
+        Err(Errors::InvalidLocale(String::from("xx"))),
+    );
+
+    // nothing was stored
+    assert_eq!(contract.get_phrase(), "");
+}
+
+
 // We can also test error situations, known as 'panic'
 // This function will raise an error (panic) with the message "A panic just happened"
 // Our test checks if indeed panic happened, and will pass if this was the case
@@ -115,3 +334,17 @@ pub fn this_will_panic() {
 
     Contract::this_will_panic();
 }
+
+
+// Functions returning Result don't panic, so we check which error was returned
+#[test]
+pub fn this_will_fail() {
+    env_setup();
+
+    let _contract: Contract = Contract::default();
+
+    assert_eq!(
+        Contract::this_will_fail(),
+        Err(Errors::AFailureHappened),
+    );
+}
@@ -4,6 +4,7 @@
 //! - panic!
 //! - vec!
 //! - setup_alloc!
+//! - contract_log! (from the contract_log crate, shared by all lessons)
 //! 
 //! 
 //! 
@@ -12,22 +13,17 @@ use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
 #[allow(unused_imports)]
 use near_sdk::{env, near_bindgen};
 
+// Prints with println! when testing, and with env::log in the virtual machine.
+// Messages below the minimum level (a cargo feature of contract_log) are removed at compile time.
+use contract_log::{contract_log, contract_panic};
 
-near_sdk::setup_alloc!();
+// Tables and key/value blocks, built with the format! specifiers from format_examples
+pub mod report;
 
+use report::{Align, KeyValues, Table, TableOptions};
 
-/// This function is used in print_examples and compiled when test option is used.
-#[cfg(test)]
-pub fn log(message: &str) {
-    println!("{}", message);
-}
 
-
-/// This function is used in print_examples and NOT compiled when test option is used.
-#[cfg(not(test))]
-pub fn log(message: &str) {
-    env::log(message.as_bytes());
-}
+near_sdk::setup_alloc!();
 
 
 #[near_bindgen]
@@ -42,7 +38,7 @@ impl Contract{
     /// Run with `cargo test -- --nocapture`, compile, deply and execute on NEAR
     /// You'll notice some of these will print out, but others will not
     pub fn print_examples() {
-        log("\n\nprint_examples:\n");
+        contract_log!(debug, "\n\nprint_examples:\n");
         println!("This println! will not output in the virtual machine");
         let a = String::from("something");
         println!("Another println! with some arguments, {}, {}, {}", 1, "thingy", a);
@@ -55,91 +51,131 @@ impl Contract{
             .as_bytes()
         );
 
-        log("A message that shows up in the virtual machine during when testing.");
+        contract_log!(info, "A message that shows up in the virtual machine during when testing.");
 
-        log(
-            &format!("Another message with arguments, {}, {}, {}.",
-                5,
-                "6",
-                format!("7"),
-            )
+        contract_log!(info, [print_arguments] "Another message with arguments, {}, {}, {}.",
+            5,
+            "6",
+            format!("7"),
         );
         
-        log("\n\n---------------------------------------------------------------------------------\n\n");
+        contract_log!(debug, "\n\n---------------------------------------------------------------------------------\n\n");
     }
 
 
     /// examples for format!
     /// check the message output for each format! use
     pub fn format_examples() {
-        log("\n\nformat_examples:\n");
+        contract_log!(debug, "\n\nformat_examples:\n");
         let message: String = format!("format returns a formatted string");
-        log(&message);
+        contract_log!(info, "{}", message);
 
         let an_arg ="third";
         let message = format!("format can receive argument using {{}}: {}, {}, {}.", 1, "second", an_arg);
     
-        log(&message);
+        contract_log!(info, "{}", message);
 
         let (first, second, third) = (1, "segundo", an_arg);
         let message = format!("We can specify arguments by variable name: {first}, {second}, {third}.");
 
-        log(&message);
+        contract_log!(info, "{}", message);
 
         let message = format!("We can specify the ordering of arguments for format: {1}, {2}, {0}.", third, first, second);
 
-        log(&message);
+        contract_log!(info, "{}", message);
 
         let (first, second, third) = (1, 2, 3);
         let message = format!("We can show integers with specified digits: {:02}, {:04}, {:6}.", first, second, third);
-        log(&message);
+        contract_log!(info, "{}", message);
 
         let message = format!("Specifying digits and argument ordering: {2:02}, {0:4}, {1:06}.", second, third, first);
-        log(&message);
+        contract_log!(info, "{}", message);
 
         let (first, second, third) = (0.1, 1.23, -2.45);
         let message = format!("Specify precision for floating points: {:.2}, {:.4}, {:.6}", first, second, third);
-        log(&message);
+        contract_log!(info, [format_precision] "{}", message);
 
         let message = format!("Specifying both precision and digits: {:2.2}, {:04.4}, {:06.6}", first, second, third);
-        log(&message);
+        contract_log!(info, "{}", message);
 
         let message = format!("Specify precision, digits and ordering of arguments: {1:02.2}, {2:4.4}, {0:06.6}", third, first, second);
-        log(&message);
+        contract_log!(info, "{}", message);
 
         let message = format!("Same as previous one but with variable names: {first:2.2}, {second:04.4}, {third:6.6}");
 
-        log(&message);
+        contract_log!(info, "{}", message);
 
-        log("\n\n----------------------------------------------------------------------\n\n");
+        contract_log!(debug, "\n\n----------------------------------------------------------------------\n\n");
+    }
+
+    /// Examples for the report module
+    /// Width, alignment and precision are the same specifiers used in format_examples
+    pub fn report_examples() {
+        contract_log!(debug, "\n\nreport_examples:\n");
+
+        let table = Table::new(vec!["Month", "Sales", "Growth"])
+            .align(1, Align::Right)
+            .align(2, Align::Right)
+            .precision(2, 1)
+            .row(vec!["January", "1200", "0"])
+            .row(vec!["February", "1530", "27.5"])
+            .row(vec!["March", "987", "-35.49"]);
+
+        contract_log!(info, "A plain text table:\n{}", table.render());
+        contract_log!(info, "The same table as Markdown:\n{}", table.markdown().render());
+
+        let status = KeyValues::new()
+            .entry("contract", "lesson_5_macro_usage")
+            .entry("version", 1)
+            .entry("description", "Examples for the most common macros")
+            .max_width(20);
+
+        contract_log!(info, "A key/value block, values truncated to 20 characters:\n{}", status.render());
+
+        contract_log!(debug, "\n\n-------------------------------------------------------------------------------\n\n");
+    }
+
+    /// Renders rows as a text table, the first row is the header.
+    /// 
+    /// options can be left out, see report::TableOptions.
+    /// e.g.: render_table '{"rows": [["Name", "Score"], ["alice", "9.5"]], "options": {"align": ["left", "right"], "precision": 2}}'
+    /// 
+    /// # Panics
+    ///  - If options.precision is higher than report::MAX_PRECISION (20).
+    pub fn render_table(rows: Vec<Vec<String>>, options: Option<TableOptions>) -> String {
+        match Table::from_rows(rows, options.unwrap_or_default()) {
+            Ok(table) => table.render(),
+            Err(error) => contract_panic!([invalid_table_options] "{}", error),
+        }
     }
 
     /// Example for panic!
     pub fn panic_example() {
-        log("\n\npanic_example:\n\n\n");
+        contract_log!(debug, "\n\npanic_example:\n\n\n");
 
-        log("The panic! macro is used just like println! and format!");
+        contract_log!(info, "The panic! macro is used just like println! and format!");
 
         let second = 2;
-        panic!("panic! with arguments: {} {} {}", 1, second, 3);
+        // contract_panic! is panic! with an ID, so tests can check it in any language
+        contract_panic!([panic_example] "panic! with arguments: {} {} {}", 1, second, 3);
 
     }
 
     /// Example for vec!
     pub fn vec_examples() {
-        log("\n\nvec_examples:\n");
+        contract_log!(debug, "\n\nvec_examples:\n");
 
         let example = vec![1, 2, 3, 4];
 
-        log(&format!("Let's print a vector with debug formatting:\n{:?}\n\n", example));
+        contract_log!(info, [vec_debug] "Let's print a vector with debug formatting:\n{:?}\n\n", example);
         
-        log(&format!("Let's print it using \"pretty print\":\n{:#?}\n\n", example));
+        contract_log!(info, "Let's print it using \"pretty print\":\n{:#?}\n\n", example);
 
-        log(&format!("We can do the same with tuples:\n{:#?}\n\n", (1, 2, 3)));
+        contract_log!(info, "We can do the same with tuples:\n{:#?}\n\n", (1, 2, 3));
 
-        log(&format!("Let's create vectors with default values:\n{:?}\n\n", vec![0;5]));
+        contract_log!(info, "Let's create vectors with default values:\n{:?}\n\n", vec![0;5]);
 
-        log("\n\n-------------------------------------------------------------------------------\n\n");
+        contract_log!(debug, "\n\n-------------------------------------------------------------------------------\n\n");
     }
 }
 
@@ -149,49 +185,7 @@ impl Contract{
 //
 // --nocapture will print everything as output, 
 // Without --test-threads, all tests will run async and so output will be confusing
+//
+// The tests are shared with the translated copies of this lesson, so they check message IDs instead of text.
 #[cfg(test)]
-mod tests{
-    use super::*;
-    use near_sdk::{
-        MockedBlockchain,
-        testing_env,
-        test_utils::VMContextBuilder,
-    };
-
-    fn env_setup(){
-        let builder: VMContextBuilder = VMContextBuilder::new();
-        testing_env!(builder.build());
-
-        // our contract is stateless, we declare it but we don't use it
-        let _contract: Contract = Contract::default();
-    }
-
-    #[test]
-    pub fn print_examples() {
-        env_setup();
-    
-        Contract::print_examples();
-    }
-
-    #[test]
-    pub fn format_examples() {
-        env_setup();
-    
-        Contract::format_examples();
-    }
-
-    #[test]
-    pub fn vec_examples() {
-        env_setup();
-    
-        Contract::vec_examples();
-    }
-
-    #[test]
-    #[should_panic(expected = "Pânico com alguns argumentos: 1 2 3")]
-    pub fn panic_example() {
-        env_setup();
-
-        Contract::panic_example();
-    }
-}
+mod tests;
@@ -0,0 +1,569 @@
+//! Renders tables and key/value blocks as text, using the width and precision specifiers of format!.
+//!
+//!  - format!("{:<10}", a) aligns a to the left, using at least 10 characters.
+//!  - format!("{:>10}", a) aligns to the right, format!("{:^10}", a) centers.
+//!  - format!("{:<width$}", a, width = 10) does the same, but the width is a variable.
+//!  - format!("{:.2}", 3.14159) shows a number with 2 decimal places. format!("{:.precision$}", ...) also works.
+//!
+//! ```
+//! use lesson_5_macro_usage::report::{Align, Table};
+//!
+//! let table = Table::new(vec!["Name", "Score"])
+//!     .align(1, Align::Right)
+//!     .precision(1, 1)
+//!     .row(vec!["alice", "9.26"])
+//!     .row(vec!["bob", "10"]);
+//!
+//! assert_eq!(
+//!     table.render(),
+//!     "Name  | Score\n------+------\nalice |   9.3\nbob   |  10.0\n",
+//! );
+//! ```
+//!
+
+use std::fmt;
+
+use near_sdk::serde::{Deserialize, Serialize};
+
+
+/// Highest number of decimal places a column can show.
+///
+/// f64 has about 17 significant digits, more decimal places would only show noise,
+/// and a large precision would build a huge string.
+pub const MAX_PRECISION: usize = 20;
+
+
+/// Returned when the options of a table are not valid.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ReportError {
+    PrecisionTooHigh(usize),
+}
+
+impl fmt::Display for ReportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReportError::PrecisionTooHigh(precision) => write!(
+                f, "Precision can't be more than {}. Got {}.", MAX_PRECISION, precision,
+            ),
+        }
+    }
+}
+
+
+/// Alignment of a column
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(rename_all = "lowercase")]
+pub enum Align {
+    #[default]
+    Left,
+    Right,
+    Center,
+}
+
+
+/// Options for a table sent as JSON, every field can be left out.
+///
+/// e.g.: {"align": ["left", "right"], "precision": 2, "max_width": 12, "markdown": true}
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(default)]
+pub struct TableOptions {
+    /// Alignment of each column, in order. Columns left out are aligned to the left.
+    pub align: Vec<Align>,
+    /// Decimal places for every cell that is a number, up to MAX_PRECISION.
+    pub precision: Option<usize>,
+    /// Cells with more characters than this are truncated.
+    pub max_width: Option<usize>,
+    /// Render a Markdown table instead of plain text.
+    pub markdown: bool,
+}
+
+
+// How each column is rendered
+#[derive(Clone, Debug, Default)]
+struct Column {
+    header: String,
+    align: Align,
+    precision: Option<usize>,
+    max_width: Option<usize>,
+}
+
+
+/// A table with a header row.
+///
+/// Rows with fewer cells than the header are completed with empty cells.
+/// Rows with more cells add columns without a header.
+#[derive(Clone, Debug, Default)]
+pub struct Table {
+    columns: Vec<Column>,
+    rows: Vec<Vec<String>>,
+    markdown: bool,
+}
+
+
+impl Table {
+    pub fn new<T: ToString>(headers: Vec<T>) -> Self {
+        let columns: Vec<Column> = headers
+            .iter()
+            .map(|header| Column { header: header.to_string(), ..Column::default() })
+            .collect();
+
+        Table {
+            columns,
+            ..Table::default()
+        }
+    }
+
+    /// Creates a table from JSON-like data, the first row is the header.
+    ///
+    /// Returns an error if options.precision is higher than MAX_PRECISION.
+    pub fn from_rows(mut rows: Vec<Vec<String>>, options: TableOptions) -> Result<Self, ReportError> {
+        if let Some(precision) = options.precision {
+            if precision > MAX_PRECISION {
+                return Err(ReportError::PrecisionTooHigh(precision));
+            }
+        }
+
+        let headers: Vec<String> = if rows.is_empty() { Vec::new() } else { rows.remove(0) };
+
+        let mut table = Table::new(headers);
+        table.rows = rows;
+        table.markdown = options.markdown;
+        table.complete_columns();
+
+        for (index, column) in table.columns.iter_mut().enumerate() {
+            column.align = options.align.get(index).copied().unwrap_or_default();
+            column.precision = options.precision;
+            column.max_width = options.max_width;
+        }
+
+        Ok(table)
+    }
+
+    /// Adds a row
+    pub fn row<T: ToString>(mut self, cells: Vec<T>) -> Self {
+        self.rows.push(cells.iter().map(|cell| cell.to_string()).collect());
+        self.complete_columns();
+
+        self
+    }
+
+    /// Aligns a column, the first column is 0
+    pub fn align(mut self, column: usize, align: Align) -> Self {
+        self.column_mut(column).align = align;
+
+        self
+    }
+
+    /// Shows numbers in a column with this many decimal places. Cells that aren't numbers are not changed.
+    ///
+    /// # Panics
+    ///  - If precision is higher than MAX_PRECISION.
+    pub fn precision(mut self, column: usize, precision: usize) -> Self {
+        assert!(precision <= MAX_PRECISION, "{}", ReportError::PrecisionTooHigh(precision));
+
+        self.column_mut(column).precision = Some(precision);
+
+        self
+    }
+
+    /// Truncates cells of a column to this many characters, ending with '…'
+    pub fn max_width(mut self, column: usize, max_width: usize) -> Self {
+        self.column_mut(column).max_width = Some(max_width);
+
+        self
+    }
+
+    /// Renders a Markdown table instead of plain text
+    pub fn markdown(mut self) -> Self {
+        self.markdown = true;
+
+        self
+    }
+
+    fn column_mut(&mut self, column: usize) -> &mut Column {
+        if column >= self.columns.len() {
+            self.columns.resize(column + 1, Column::default());
+        }
+
+        &mut self.columns[column]
+    }
+
+    // Rows can be longer than the header
+    fn complete_columns(&mut self) {
+        let length: usize = self.rows.iter().map(|row| row.len()).max().unwrap_or(0);
+
+        if length > self.columns.len() {
+            self.columns.resize(length, Column::default());
+        }
+    }
+
+    // Each cell of a row after precision and truncation, with empty cells for the missing ones
+    // Precision is not used for the header
+    fn format_row(&self, row: &[String], is_header: bool) -> Vec<String> {
+        self.columns
+            .iter()
+            .enumerate()
+            .map(|(index, column)| {
+                let cell: &str = row.get(index).map(|cell| &cell[..]).unwrap_or("");
+
+                let cell: String = match (column.precision, cell.trim().parse::<f64>()) {
+                    (Some(precision), Ok(number)) if !is_header => format!("{:.precision$}", number, precision = precision),
+                    _ => String::from(cell),
+                };
+
+                let cell: String = match column.max_width {
+                    Some(max_width) => truncate(&cell, max_width),
+                    None => cell,
+                };
+
+                if self.markdown {
+                    cell.replace('|', "\\|")
+                } else {
+                    cell
+                }
+            })
+            .collect()
+    }
+
+    /// Returns the table as text, each line ends with '\n'
+    ///
+    /// A table without any column is an empty string.
+    pub fn render(&self) -> String {
+        if self.columns.is_empty() {
+            return String::new();
+        }
+
+        let headers: Vec<String> = self.columns.iter().map(|column| column.header.clone()).collect();
+        let headers: Vec<String> = self.format_row(&headers, true);
+        let rows: Vec<Vec<String>> = self.rows.iter().map(|row| self.format_row(row, false)).collect();
+
+        // The width of a column is the width of its largest cell
+        let mut widths: Vec<usize> = headers.iter().map(|cell| width(cell)).collect();
+        for row in &rows {
+            for (index, cell) in row.iter().enumerate() {
+                widths[index] = widths[index].max(width(cell));
+            }
+        }
+
+        if self.markdown {
+            // Markdown needs at least 3 dashes for each column
+            for width in widths.iter_mut() {
+                *width = (*width).max(3);
+            }
+        }
+
+        let mut lines: Vec<String> = Vec::with_capacity(rows.len() + 2);
+
+        lines.push(self.render_line(&headers, &widths));
+        lines.push(self.render_separator(&widths));
+
+        for row in &rows {
+            lines.push(self.render_line(row, &widths));
+        }
+
+        let mut result: String = lines.join("\n");
+        result.push('\n');
+
+        result
+    }
+
+    fn render_line(&self, cells: &[String], widths: &[usize]) -> String {
+        let cells: Vec<String> = cells
+            .iter()
+            .zip(widths)
+            .zip(&self.columns)
+            .map(|((cell, width), column)| pad(cell, *width, column.align))
+            .collect();
+
+        if self.markdown {
+            format!("| {} |", cells.join(" | "))
+        } else {
+            // No spaces are left at the end of the line
+            String::from(cells.join(" | ").trim_end())
+        }
+    }
+
+    fn render_separator(&self, widths: &[usize]) -> String {
+        if !self.markdown {
+            let dashes: Vec<String> = widths.iter().map(|width| "-".repeat(*width)).collect();
+
+            return dashes.join("-+-");
+        }
+
+        let dashes: Vec<String> = widths
+            .iter()
+            .zip(&self.columns)
+            .map(|(width, column)| match column.align {
+                Align::Left => format!(":{}", "-".repeat(width - 1)),
+                Align::Right => format!("{}:", "-".repeat(width - 1)),
+                Align::Center => format!(":{}:", "-".repeat(width - 2)),
+            })
+            .collect();
+
+        format!("| {} |", dashes.join(" | "))
+    }
+}
+
+
+/// Key and value pairs, one for each line, with the values aligned.
+///
+/// ```
+/// use lesson_5_macro_usage::report::KeyValues;
+///
+/// let block = KeyValues::new()
+///     .entry("owner", "alice.near")
+///     .entry("balance", format!("{:.2}", 10.5));
+///
+/// assert_eq!(block.render(), "owner   : alice.near\nbalance : 10.50\n");
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct KeyValues {
+    entries: Vec<(String, String)>,
+    align: Align,
+    max_width: Option<usize>,
+}
+
+
+impl KeyValues {
+    pub fn new() -> Self {
+        KeyValues::default()
+    }
+
+    /// Adds a line
+    pub fn entry<K: ToString, V: ToString>(mut self, key: K, value: V) -> Self {
+        self.entries.push((key.to_string(), value.to_string()));
+
+        self
+    }
+
+    /// Aligns the keys, the default is left
+    pub fn align(mut self, align: Align) -> Self {
+        self.align = align;
+
+        self
+    }
+
+    /// Truncates values to this many characters, ending with '…'
+    pub fn max_width(mut self, max_width: usize) -> Self {
+        self.max_width = Some(max_width);
+
+        self
+    }
+
+    /// Returns every line, each one ends with '\n'
+    pub fn render(&self) -> String {
+        let key_width: usize = self.entries.iter().map(|(key, _)| width(key)).max().unwrap_or(0);
+
+        let mut result = String::new();
+
+        for (key, value) in &self.entries {
+            let value: String = match self.max_width {
+                Some(max_width) => truncate(value, max_width),
+                None => value.clone(),
+            };
+
+            result.push_str(format!("{} : {}", pad(key, key_width, self.align), value).trim_end());
+            result.push('\n');
+        }
+
+        result
+    }
+}
+
+
+// Number of characters, which is what format! uses for width
+fn width(text: &str) -> usize {
+    text.chars().count()
+}
+
+
+fn pad(text: &str, width: usize, align: Align) -> String {
+    match align {
+        Align::Left => format!("{:<width$}", text, width = width),
+        Align::Right => format!("{:>width$}", text, width = width),
+        Align::Center => format!("{:^width$}", text, width = width),
+    }
+}
+
+
+// Cuts text to max_width characters, the last one being '…'
+fn truncate(text: &str, max_width: usize) -> String {
+    if width(text) <= max_width {
+        return String::from(text);
+    }
+
+    if max_width == 0 {
+        return String::new();
+    }
+
+    let mut result: String = text.chars().take(max_width - 1).collect();
+    result.push('…');
+
+    result
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn plain_table() {
+        let table = Table::new(vec!["Item", "Qty", "Price"])
+            .align(1, Align::Center)
+            .align(2, Align::Right)
+            .precision(2, 2)
+            .row(vec!["Apple", "3", "1.5"])
+            .row(vec!["Watermelon", "10", "12"]);
+
+        assert_eq!(
+            table.render(),
+            concat!(
+                "Item       | Qty | Price\n",
+                "-----------+-----+------\n",
+                "Apple      |  3  |  1.50\n",
+                "Watermelon | 10  | 12.00\n",
+            ),
+        );
+    }
+
+    #[test]
+    pub fn markdown_table() {
+        let table = Table::new(vec!["Key", "Value"])
+            .align(1, Align::Right)
+            .markdown()
+            .row(vec!["a|b", "1"]);
+
+        assert_eq!(
+            table.render(),
+            concat!(
+                "| Key  | Value |\n",
+                "| :--- | ----: |\n",
+                "| a\\|b |     1 |\n",
+            ),
+        );
+    }
+
+    #[test]
+    pub fn truncate_and_ragged_rows() {
+        let table = Table::new(vec!["Name"])
+            .max_width(0, 5)
+            .row(vec!["Maximilian", "extra"])
+            .row(Vec::<String>::new());
+
+        assert_eq!(
+            table.render(),
+            concat!(
+                "Name  |\n",
+                "------+------\n",
+                "Maxi… | extra\n",
+                "      |\n",
+            ),
+        );
+
+        // Characters are counted, not bytes
+        assert_eq!(truncate("Conceição", 6), "Conce…");
+        assert_eq!(truncate("Conceição", 9), "Conceição");
+    }
+
+    #[test]
+    pub fn from_rows() {
+        let rows: Vec<Vec<String>> = vec![
+            vec![String::from("Account"), String::from("Balance")],
+            vec![String::from("alice.near"), String::from("10.456")],
+        ];
+
+        let options: TableOptions = near_sdk::serde_json::from_str(
+            r#"{"align": ["left", "right"], "precision": 1}"#
+        ).unwrap();
+
+        assert_eq!(
+            Table::from_rows(rows, options).unwrap().render(),
+            concat!(
+                "Account    | Balance\n",
+                "-----------+--------\n",
+                "alice.near |    10.5\n",
+            ),
+        );
+
+        assert_eq!(Table::from_rows(Vec::new(), TableOptions::default()).unwrap().render(), "");
+
+        // Only the header, there are no rows
+        let header: Vec<Vec<String>> = vec![vec![String::from("Account")]];
+        assert_eq!(Table::from_rows(header, TableOptions::default()).unwrap().render(), "Account\n-------\n");
+    }
+
+    #[test]
+    pub fn precision_too_high() {
+        let options = TableOptions { precision: Some(MAX_PRECISION + 1), ..TableOptions::default() };
+
+        assert_eq!(
+            Table::from_rows(Vec::new(), options).unwrap_err(),
+            ReportError::PrecisionTooHigh(MAX_PRECISION + 1),
+        );
+
+        let options = TableOptions { precision: Some(MAX_PRECISION), ..TableOptions::default() };
+        assert!(Table::from_rows(Vec::new(), options).is_ok());
+    }
+
+    #[test]
+    #[should_panic(expected = "Precision can't be more than 20. Got 1000.")]
+    pub fn precision_too_high_builder() {
+        let _ = Table::new(vec!["Score"]).precision(0, 1000);
+    }
+
+    #[test]
+    pub fn key_values() {
+        let block = KeyValues::new()
+            .align(Align::Right)
+            .max_width(8)
+            .entry("id", 7)
+            .entry("description", "A long description");
+
+        assert_eq!(
+            block.render(),
+            concat!(
+                "         id : 7\n",
+                "description : A long …\n",
+            ),
+        );
+    }
+
+    #[test]
+    pub fn report_examples() {
+        crate::Contract::report_examples();
+    }
+
+    #[test]
+    pub fn render_table() {
+        let rows: Vec<Vec<String>> = vec![
+            vec![String::from("Name"), String::from("Score")],
+            vec![String::from("alice"), String::from("9.5")],
+        ];
+
+        let options = TableOptions {
+            align: vec![Align::Left, Align::Right],
+            precision: Some(2),
+            markdown: true,
+            ..TableOptions::default()
+        };
+
+        assert_eq!(
+            crate::Contract::render_table(rows, Some(options)),
+            "| Name  | Score |\n| :---- | ----: |\n| alice |  9.50 |\n",
+        );
+    }
+
+    #[test]
+    pub fn render_table_precision_too_high() {
+        let options = TableOptions { precision: Some(21), ..TableOptions::default() };
+
+        contract_log::assert_panics!(
+            invalid_table_options,
+            crate::Contract::render_table(Vec::new(), Some(options)),
+            [21]
+        );
+    }
+}
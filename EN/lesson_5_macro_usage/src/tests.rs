@@ -0,0 +1,54 @@
+//! Tests shared by every translation of this lesson.
+//!
+//! ES/ and PT-BR/ include this file with `#[path]`, so messages are checked by their ID and arguments,
+//! never by their text.
+
+use crate::Contract;
+use contract_log::{assert_logged, assert_panics};
+use near_sdk::{
+    MockedBlockchain,
+    testing_env,
+    test_utils::VMContextBuilder,
+};
+
+fn env_setup(){
+    let builder: VMContextBuilder = VMContextBuilder::new();
+    testing_env!(builder.build());
+
+    // our contract is stateless, we declare it but we don't use it
+    let _contract: Contract = Contract::default();
+}
+
+#[test]
+pub fn print_examples() {
+    env_setup();
+
+    Contract::print_examples();
+
+    assert_logged!(print_arguments, [5, 6, 7]);
+}
+
+#[test]
+pub fn format_examples() {
+    env_setup();
+
+    Contract::format_examples();
+
+    assert_logged!(format_precision, ["0.10", "1.2300", "-2.450000"]);
+}
+
+#[test]
+pub fn vec_examples() {
+    env_setup();
+
+    Contract::vec_examples();
+
+    assert_logged!(vec_debug, [1, 2, 3, 4]);
+}
+
+#[test]
+pub fn panic_example() {
+    env_setup();
+
+    assert_panics!(panic_example, Contract::panic_example(), [1, 2, 3]);
+}
@@ -0,0 +1,467 @@
+//! Audit log: every change made to users, orders and stored values, who made it and when.
+//!
+//! Each call function that changes state records an Action, with the caller (actor) and
+//! `env::block_timestamp`. The log only grows, so records are in order of time and their ids
+//! are their positions in the log.
+//!
+//! Listing with filters could read the whole log to fill a page, so a call reads at most MAX_SCAN records
+//! and returns where it stopped, in ActionPage::next_index.
+//!
+
+use near_sdk::{
+    borsh::{self, BorshDeserialize, BorshSerialize},
+    env,
+    near_bindgen,
+    serde::{ Serialize, Deserialize },
+    AccountId,
+};
+
+use named_variants_derive::NamedVariants;
+
+use crate::{
+    log_call,
+    model::{Example1Kind, Example2User, Role},
+    order::OrderStatus,
+    permission::Permission,
+    users::{DEFAULT_LIST_LIMIT, MAX_LIST_LIMIT},
+    Contract,
+};
+
+
+/// Something an account did. Each variant holds its target, the user or order changed.
+#[derive(BorshDeserialize, BorshSerialize, Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub enum Action {
+    UserCreated { user_id: u32, role: Role },
+    UserUpdated { user_id: u32 },
+    UserDeleted { user_id: u32 },
+    PermissionGranted { user_id: u32, permission: Permission },
+    PermissionRevoked { user_id: u32, permission: Permission },
+    PasswordChanged { user_id: u32 },
+    OrderPlaced { client_id: u32, order_id: u32 },
+    OrderCancelled { client_id: u32, order_id: u32 },
+    OrderAdvanced { client_id: u32, order_id: u32, status: OrderStatus },
+    /// A free text action, from before actions were recorded. See migrate_plaintext_passwords.
+    Imported { user_id: u32, description: String },
+    ValuePut { key: String, kind: Example1Kind },
+    ValueRemoved { key: String },
+}
+
+/// The variant of an Action, without its values.
+///
+/// Used to filter the log, e.g. list_actions with kind "OrderAdvanced".
+#[derive(Clone, Copy, Debug, Deserialize, NamedVariants, PartialEq, Eq, Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub enum ActionKind {
+    UserCreated,
+    UserUpdated,
+    UserDeleted,
+    PermissionGranted,
+    PermissionRevoked,
+    PasswordChanged,
+    OrderPlaced,
+    OrderCancelled,
+    OrderAdvanced,
+    Imported,
+    ValuePut,
+    ValueRemoved,
+}
+
+impl Action {
+    pub fn kind(&self) -> ActionKind {
+        match self {
+            Action::UserCreated { .. } => ActionKind::UserCreated,
+            Action::UserUpdated { .. } => ActionKind::UserUpdated,
+            Action::UserDeleted { .. } => ActionKind::UserDeleted,
+            Action::PermissionGranted { .. } => ActionKind::PermissionGranted,
+            Action::PermissionRevoked { .. } => ActionKind::PermissionRevoked,
+            Action::PasswordChanged { .. } => ActionKind::PasswordChanged,
+            Action::OrderPlaced { .. } => ActionKind::OrderPlaced,
+            Action::OrderCancelled { .. } => ActionKind::OrderCancelled,
+            Action::OrderAdvanced { .. } => ActionKind::OrderAdvanced,
+            Action::Imported { .. } => ActionKind::Imported,
+            Action::ValuePut { .. } => ActionKind::ValuePut,
+            Action::ValueRemoved { .. } => ActionKind::ValueRemoved,
+        }
+    }
+
+    /// Id of the user changed. For orders, the client. None for stored values, their key is the target.
+    pub fn target(&self) -> Option<u32> {
+        match self {
+            Action::UserCreated { user_id, .. }
+            | Action::UserUpdated { user_id }
+            | Action::UserDeleted { user_id }
+            | Action::PermissionGranted { user_id, .. }
+            | Action::PermissionRevoked { user_id, .. }
+            | Action::PasswordChanged { user_id }
+            | Action::Imported { user_id, .. } => Some(*user_id),
+            Action::OrderPlaced { client_id, .. }
+            | Action::OrderCancelled { client_id, .. }
+            | Action::OrderAdvanced { client_id, .. } => Some(*client_id),
+            Action::ValuePut { .. } | Action::ValueRemoved { .. } => None,
+        }
+    }
+}
+
+
+/// Most records list_actions and get_actions read in one call, matching or not.
+pub const MAX_SCAN: u64 = MAX_LIST_LIMIT as u64 * 10;
+
+
+/// An Action in the log.
+#[derive(BorshDeserialize, BorshSerialize, Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ActionRecord {
+    /// Position in the log
+    pub id: u64,
+    /// Account that made the call
+    pub actor: AccountId,
+    /// User linked to the actor account when the call was made. None for the owner and accounts without a user.
+    ///
+    /// Accounts can move to another user, so the log keeps the user, not only the account.
+    pub actor_user_id: Option<u32>,
+    pub block_timestamp: u64,
+    pub action: Action,
+}
+
+/// A page of the log.
+#[derive(BorshDeserialize, BorshSerialize, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ActionPage {
+    pub records: Vec<ActionRecord>,
+    /// from_index of the next page. None if there's nothing left to read.
+    ///
+    /// Can be Some even if records has less than limit: the call read MAX_SCAN records without filling the page.
+    pub next_index: Option<u64>,
+}
+
+
+#[near_bindgen]
+impl Contract{
+    /// Lists the log in order of time. Arguments left out don't filter.
+    ///
+    ///  - actor: only actions made by this account.
+    ///  - kind: only this ActionKind, e.g. "PermissionGranted".
+    ///  - since: only actions with block_timestamp greater or equal.
+    ///  - until: only actions with block_timestamp lower.
+    ///  - from_index: first id to read. Default 0.
+    ///  - limit: most actions returned. Default DEFAULT_LIST_LIMIT, at most MAX_LIST_LIMIT.
+    ///
+    /// Reads at most MAX_SCAN records. To get the next page, call again with from_index set to next_index.
+    ///
+    /// # Panics
+    ///  - If kind isn't a valid name.
+    pub fn list_actions(
+        &self,
+        actor: Option<AccountId>,
+        kind: Option<String>,
+        since: Option<u64>,
+        until: Option<u64>,
+        from_index: Option<u64>,
+        limit: Option<u32>,
+    ) -> ActionPage {
+        log_call("list_actions");
+
+        let limit: u32 = limit.unwrap_or(DEFAULT_LIST_LIMIT).min(MAX_LIST_LIMIT);
+        let kind: Option<ActionKind> = kind.map(|kind| match kind.parse() {
+            Ok(kind) => kind,
+            Err(err) => panic!("{}", err),
+        });
+
+        let start: u64 = match since {
+            Some(since) => self.first_action_since(since),
+            None => 0,
+        };
+
+        let is_after_until = |record: &ActionRecord| match until {
+            Some(until) => record.block_timestamp >= until,
+            None => false,
+        };
+
+        let matches = |record: &ActionRecord| {
+            let actor_matches: bool = match &actor {
+                Some(actor) => &record.actor == actor,
+                None => true,
+            };
+            let kind_matches: bool = match kind {
+                Some(kind) => record.action.kind() == kind,
+                None => true,
+            };
+
+            actor_matches && kind_matches
+        };
+
+        self.scan_actions(start.max(from_index.unwrap_or(0)), limit, is_after_until, matches)
+    }
+}
+
+
+impl Contract {
+    /// Adds an action made by the caller to the log.
+    pub(crate) fn record(&mut self, action: Action) {
+        let record = ActionRecord {
+            id: self.actions.len(),
+            actor: env::predecessor_account_id(),
+            actor_user_id: self.user_ids.get(&env::predecessor_account_id()),
+            block_timestamp: env::block_timestamp(),
+            action,
+        };
+
+        self.actions.push(&record);
+    }
+
+    /// Actions made by an Admin or Employee, and actions about them, in order of time.
+    ///
+    /// An action is made by the user if its account was linked to the user at the time.
+    /// An action is about the user if its target is the user, e.g. PasswordChanged or Imported.
+    ///
+    /// from_index and limit work the same as in list_actions, and so does MAX_SCAN.
+    ///
+    /// Returns an error for Clients. Their orders are in list_actions.
+    pub(crate) fn user_actions(&self, user_id: u32, from_index: Option<u64>, limit: Option<u32>) -> Result<ActionPage, String> {
+        // If Client, return an error.
+        // If Admin or Employee, return the page of its actions.
+        if let Example2User::Client { .. } = self.stored_user(user_id) {
+            return Err(String::from("User is Client"));
+        }
+
+        let limit: u32 = limit.unwrap_or(DEFAULT_LIST_LIMIT).min(MAX_LIST_LIMIT);
+
+        Ok(self.scan_actions(
+            from_index.unwrap_or(0),
+            limit,
+            |_| false,
+            |record| record.actor_user_id == Some(user_id) || record.action.target() == Some(user_id),
+        ))
+    }
+
+    // Reads the log from start, keeping up to limit records that match.
+    // Stops at the first record where is_end is true, or after reading MAX_SCAN records.
+    fn scan_actions(
+        &self,
+        start: u64,
+        limit: u32,
+        is_end: impl Fn(&ActionRecord) -> bool,
+        matches: impl Fn(&ActionRecord) -> bool,
+    ) -> ActionPage {
+        let len: u64 = self.actions.len();
+        let scan_end: u64 = len.min(start.saturating_add(MAX_SCAN));
+
+        let mut records: Vec<ActionRecord> = Vec::new();
+        let mut index: u64 = start;
+
+        while index < scan_end && records.len() < limit as usize {
+            let record: ActionRecord = match self.actions.get(index) {
+                Some(record) => record,
+                None => break,
+            };
+
+            if is_end(&record) {
+                return ActionPage { records, next_index: None };
+            }
+
+            if matches(&record) {
+                records.push(record);
+            }
+
+            index += 1;
+        }
+
+        let next_index: Option<u64> = if index < len { Some(index) } else { None };
+
+        ActionPage { records, next_index }
+    }
+
+    // Index of the first action with block_timestamp >= since.
+    // Timestamps never decrease along the log, so a binary search finds it.
+    fn first_action_since(&self, since: u64) -> u64 {
+        let (mut low, mut high) = (0, self.actions.len());
+
+        while low < high {
+            let middle: u64 = low + (high - low) / 2;
+
+            match self.actions.get(middle) {
+                Some(record) if record.block_timestamp < since => low = middle + 1,
+                _ => high = middle,
+            }
+        }
+
+        low
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{env_setup, env_setup_at, env_setup_caller};
+    use contract_log::testing::panic_message;
+
+    fn kinds(records: &[ActionRecord]) -> Vec<ActionKind> {
+        records.iter().map(|record| record.action.kind()).collect()
+    }
+
+    // Changes made by the owner at 100, alice.near at 200 and bob.near at 300
+    fn contract_with_actions() -> Contract {
+        env_setup_at("stiltztinkerstein", 100);
+        let mut contract = Contract::default();
+
+        contract.create_employee(String::from("alice.near"), String::from("Alice"), String::from("pass"), vec![String::from("orders")]);
+        contract.create_client(String::from("bob.near"), String::from("Bob"));
+        contract.grant_permission(0, String::from("users"));
+
+        env_setup_at("alice.near", 200);
+        contract.update_user(1, Some(String::from("Robert")), None);
+        contract.change_password(0, String::from("pass"), String::from("new pass"));
+
+        env_setup_at("bob.near", 300);
+        contract.place_order(String::from("A keyboard"));
+
+        env_setup_at("alice.near", 300);
+        contract.advance_order(1, 0, String::from("paid"));
+
+        contract
+    }
+
+    #[test]
+    fn every_change_is_recorded() {
+        let contract = contract_with_actions();
+
+        let records: Vec<ActionRecord> = contract.list_actions(None, None, None, None, None, None).records;
+
+        assert_eq!(records.iter().map(|record| record.id).collect::<Vec<u64>>(), (0..7).collect::<Vec<u64>>());
+        assert_eq!(records[0].action, Action::UserCreated { user_id: 0, role: Role::Employee });
+        assert_eq!(records[2].action, Action::PermissionGranted { user_id: 0, permission: Permission::ManageUsers });
+        assert_eq!(records[6].action, Action::OrderAdvanced { client_id: 1, order_id: 0, status: OrderStatus::Paid });
+
+        let actors: Vec<&str> = records.iter().map(|record| record.actor.as_str()).collect();
+        assert_eq!(actors, vec!["stiltztinkerstein", "stiltztinkerstein", "stiltztinkerstein", "alice.near", "alice.near", "bob.near", "alice.near"]);
+
+        let targets: Vec<Option<u32>> = records.iter().map(|record| record.action.target()).collect();
+        assert_eq!(targets, vec![Some(0), Some(1), Some(0), Some(1), Some(0), Some(1), Some(1)]);
+    }
+
+    #[test]
+    fn filters() {
+        let contract = contract_with_actions();
+
+        let by_alice = contract.list_actions(Some(String::from("alice.near")), None, None, None, None, None).records;
+        assert_eq!(kinds(&by_alice), vec![ActionKind::UserUpdated, ActionKind::PasswordChanged, ActionKind::OrderAdvanced]);
+
+        let created = contract.list_actions(None, Some(String::from("usercreated")), None, None, None, None).records;
+        assert_eq!(kinds(&created), vec![ActionKind::UserCreated, ActionKind::UserCreated]);
+
+        // [200, 300)
+        let in_range = contract.list_actions(None, None, Some(200), Some(300), None, None).records;
+        assert_eq!(in_range.iter().map(|record| record.id).collect::<Vec<u64>>(), vec![3, 4]);
+        assert!(contract.list_actions(None, None, Some(301), None, None, None).records.is_empty());
+
+        let combined = contract.list_actions(Some(String::from("alice.near")), None, Some(250), None, None, None).records;
+        assert_eq!(kinds(&combined), vec![ActionKind::OrderAdvanced]);
+
+        // pages
+        let page = contract.list_actions(None, None, None, None, Some(2), Some(2));
+        assert_eq!(page.records.iter().map(|record| record.id).collect::<Vec<u64>>(), vec![2, 3]);
+        assert_eq!(page.next_index, Some(4));
+
+        let last_page = contract.list_actions(None, None, None, None, Some(4), None);
+        assert_eq!(last_page.records.len(), 3);
+        assert_eq!(last_page.next_index, None);
+    }
+
+    #[test]
+    fn invalid_kind() {
+        let contract = contract_with_actions();
+
+        let message: String = panic_message(|| { contract.list_actions(None, Some(String::from("Signed in")), None, None, None, None); }).unwrap();
+        assert!(message.contains("Signed in"), "{}", message);
+    }
+
+    #[test]
+    fn user_actions() {
+        let contract = contract_with_actions();
+
+        // Made by alice.near, or about her user
+        assert_eq!(
+            kinds(&contract.get_actions(0, None, None).records),
+            vec![ActionKind::UserCreated, ActionKind::PermissionGranted, ActionKind::UserUpdated, ActionKind::PasswordChanged, ActionKind::OrderAdvanced],
+        );
+        assert_eq!(contract.user_actions(1, None, None), Err(String::from("User is Client")));
+
+        // pages
+        let page: ActionPage = contract.get_actions(0, Some(3), Some(2));
+        assert_eq!(page.records.iter().map(|record| record.id).collect::<Vec<u64>>(), vec![3, 4]);
+        assert_eq!(page.next_index, Some(5));
+        assert_eq!(contract.get_actions(0, Some(5), None).records.iter().map(|record| record.id).collect::<Vec<u64>>(), vec![6]);
+    }
+
+    #[test]
+    fn user_actions_after_account_moves() {
+        let mut contract = contract_with_actions();
+
+        // Alice's user moves to another account, and alice.near is given to a new employee
+        env_setup_at("stiltztinkerstein", 400);
+        contract.update_user(0, None, Some(String::from("dave.near")));
+        let carol: u32 = contract.create_employee(String::from("alice.near"), String::from("Carol"), String::from("pass"), Vec::new());
+
+        env_setup_at("alice.near", 500);
+        contract.change_password(carol, String::from("pass"), String::from("new pass"));
+
+        // Carol doesn't get Alice's history
+        assert_eq!(
+            kinds(&contract.get_actions(carol, None, None).records),
+            vec![ActionKind::UserCreated, ActionKind::PasswordChanged],
+        );
+
+        // Alice keeps it
+        assert_eq!(
+            kinds(&contract.get_actions(0, None, None).records),
+            vec![ActionKind::UserCreated, ActionKind::PermissionGranted, ActionKind::UserUpdated, ActionKind::PasswordChanged, ActionKind::OrderAdvanced, ActionKind::UserUpdated],
+        );
+    }
+
+    #[test]
+    fn failed_calls_are_not_recorded() {
+        let mut contract = contract_with_actions();
+
+        env_setup_caller("bob.near");
+        assert!(panic_message(|| contract.cancel_order(0)).is_some());
+
+        env_setup();
+        assert_eq!(contract.list_actions(None, None, None, None, None, None).records.len(), 7);
+    }
+
+    #[test]
+    fn scan_is_bounded() {
+        env_setup();
+        let mut contract = Contract::default();
+
+        for index in 0..MAX_SCAN + 5 {
+            // a fresh context every 10 users, so the test doesn't run out of gas
+            if index % 10 == 0 {
+                env_setup();
+            }
+
+            contract.create_client(format!("user{}.near", index), format!("Client {}", index));
+        }
+
+        // nothing matches, the call stops after MAX_SCAN records and says where to continue
+        let page: ActionPage = contract.list_actions(None, Some(String::from("PasswordChanged")), None, None, None, None);
+        assert!(page.records.is_empty());
+        assert_eq!(page.next_index, Some(MAX_SCAN));
+
+        let page: ActionPage = contract.list_actions(None, Some(String::from("PasswordChanged")), None, None, page.next_index, None);
+        assert!(page.records.is_empty());
+        assert_eq!(page.next_index, None);
+
+        // the same for the actions of a user
+        let admin: u32 = contract.create_admin(String::from("admin.near"), String::from("Admin"), String::from("pass"));
+        let page: ActionPage = contract.get_actions(admin, None, None);
+        assert!(page.records.is_empty());
+        assert_eq!(page.next_index, Some(MAX_SCAN));
+
+        let page: ActionPage = contract.get_actions(admin, page.next_index, None);
+        assert_eq!(kinds(&page.records), vec![ActionKind::UserCreated]);
+        assert_eq!(page.next_index, None);
+    }
+}
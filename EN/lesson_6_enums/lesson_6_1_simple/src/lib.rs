@@ -1,60 +1,151 @@
 use near_sdk::{
     borsh::{self, BorshDeserialize, BorshSerialize},
-    serde::{Deserialize, Serialize},
+    collections::{
+        LookupMap,
+        TreeMap,
+        Vector,
+    },
+    BorshStorageKey,
 };
 #[allow(unused_imports)]
-use near_sdk::{env, near_bindgen};
+use near_sdk::{env, near_bindgen, AccountId};
 
 near_sdk::setup_alloc!();
 
+pub mod audit;
+mod migration;
 mod model;
+pub mod order;
+mod password;
+pub mod permission;
+pub mod store;
+mod users;
 
 use model::{
     Example0,
+    Example0Error,
     Example1,
     Example2User,
-    log,
 };
 
+use audit::ActionRecord;
+use permission::{Permission, Permissions};
+
+use contract_log::{contract_log, contract_panic};
+
 
 /// logs the function being called
 pub fn log_call(name: &str){
-    log(&format!("Calling {}", name));
+    contract_log!(debug, "Calling {}", name);
+}
+
+
+/// Each TreeMap or LookupMap needs a unique key.
+#[derive(BorshStorageKey, BorshSerialize)]
+enum StorageKey {
+    Users,
+    UserIds,
+    UserAccounts,
+    Actions,
+    Values,
 }
 
 
+/// Smart Contract API
+/// 
+/// Example0 and Example1 are single values. Example2User is a registry of users, keyed by id,
+/// see the module users for its functions. values is a keyed store of Example1, see the module store.
+/// Every change to users, orders and values is recorded in actions, see the module audit.
 #[near_bindgen]
-#[derive(Clone, Default, BorshDeserialize, BorshSerialize, Serialize, Deserialize)]
-#[serde(crate = "near_sdk::serde")]
+#[derive(BorshDeserialize, BorshSerialize)]
 pub struct Contract {
     example_0: Example0,
     example_1: Example1,
-    example_2_user: Example2User,
+    // every user, by id
+    users: TreeMap<u32, Example2User>,
+    // the account of each user, and the user of each account. require_permission uses these
+    user_ids: LookupMap<AccountId, u32>,
+    user_accounts: LookupMap<u32, AccountId>,
+    // id for the next user created
+    next_user_id: u32,
+    // audit log, in order of time
+    actions: Vector<ActionRecord>,
+    // configuration values, by key
+    values: TreeMap<String, Example1>,
+}
+
+impl Default for Contract {
+    fn default() -> Self {
+        Contract {
+            example_0: Example0::default(),
+            example_1: Example1::default(),
+            users: TreeMap::new(StorageKey::Users),
+            user_ids: LookupMap::new(StorageKey::UserIds),
+            user_accounts: LookupMap::new(StorageKey::UserAccounts),
+            next_user_id: 0,
+            actions: Vector::new(StorageKey::Actions),
+            values: TreeMap::new(StorageKey::Values),
+        }
+    }
+}
+
+
+impl Contract {
+    /// Panics unless the caller has this permission.
+    /// 
+    /// Call functions that need a permission start with this guard.
+    /// The owner (the contract account) has every permission.
+    /// 
+    /// # Panics
+    ///  - If the caller isn't a stored user, or doesn't have the permission.
+    pub fn require_permission(&self, permission: Permission) {
+        if !self.caller_permissions().contains(permission) {
+            contract_panic!([missing_permission] "{} doesn't have permission {}.", env::predecessor_account_id(), permission);
+        }
+    }
+
+    /// Permissions of the caller. Every permission for the owner, none if the caller isn't a stored user.
+    pub fn caller_permissions(&self) -> Permissions {
+        let predecessor: AccountId = env::predecessor_account_id();
+
+        if predecessor == env::current_account_id() {
+            return Permissions::all();
+        }
+
+        self.user_ids.get(&predecessor)
+            .and_then(|user_id| self.users.get(&user_id))
+            .and_then(|user| user.permissions().copied())
+            .unwrap_or_default()
+    }
+
+    // assert the owner is the caller
+    fn assert_owner_only(&self) {
+        let predecessor: AccountId = env::predecessor_account_id();
+        let owner_id: AccountId = env::current_account_id();
+
+        assert_eq!(predecessor, owner_id, "Only owner's account is allowed to make this function call.");
+    }
 }
 
 
 #[near_bindgen]
 impl Contract{
-    /// We can use this function to match Strings and string slices &str
+    /// We can use this function to parse Strings into enums
     /// return 1, 2, 3, 4, 5, if the parameter given is one of those values
-    /// otherwise, panic!
-    pub fn string_match_example(&self, arg: String) -> u32 {
-
-        // Handle &String as &str
-        return match &arg as &str {
-            "1" => 1,
-            "2" => 2,
-            "3" => 3,
-            "4" => 4,
-            "5" => 5,
-            value => panic!("Received {}. Must be 1, 2, 3, 4 or 5.", value),
-        }
+    /// otherwise, an Example0Error.
+    ///
+    /// In JSON, the result is {"Ok": 3} or {"Err": {"OutOfRange": 6}}.
+    pub fn string_match_example(&self, arg: String) -> Result<u32, Example0Error> {
+        // parse calls Example0::from_str, ? returns the error if there's one
+        let value: Example0 = arg.parse()?;
+
+        Ok(value.get_number())
     }
 
     /// Returns example_0.
     pub fn get_example_0(&self) -> Example0 {
         log_call("get_example_0");
-        self.example_0.clone()
+        self.example_0
     }
 
     /// Returns example_1.
@@ -63,12 +154,6 @@ impl Contract{
         self.example_1.clone()
     }
 
-    /// Returns example_2.
-    pub fn get_example_2(&self) -> Example2User {
-        log_call("get_example_2");
-        self.example_2_user.clone()
-    }
-
     /// Call Example0::get_number.
     pub fn example_0_get_number(&self) -> u32 {
         log_call("get_example_0_get_number");
@@ -113,37 +198,6 @@ impl Contract{
         log_call("get_example_1_has_an_odd_number");
         self.example_1.has_an_odd_number()
     }
-
-    /// Calls Example2User::get_name.
-    pub fn example_2_get_name(&self) -> String {
-        log_call("get_example_2_get_name");
-        self.example_2_user.get_name()
-    }
-
-    /// Calls Example2User::has_permission.
-    pub fn example_2_has_permission(&self, permission: String) -> bool {
-        log_call("get_example_2_has_permission");
-        self.example_2_user.has_permission(permission)
-    }
-
-    /// Calls Example2User::get_actions.
-    /// 
-    /// 
-    /// When returing vec, the default serializer is serde (serde::json).
-    /// So let's be explicit about using borsh.
-    #[result_serializer(borsh)]
-    pub fn example_2_get_actions(&self) -> Vec<String>{
-        log_call("get_example_2_get_actions");
-
-        let result = self.example_2_user.get_actions();
-
-        let result: Vec<String> = match result {
-            Err(err) => panic!("Error: {}\n", err),
-            Ok(value) => value,
-        };
-
-        result
-    }
 }
 
 #[cfg(test)]
@@ -158,7 +212,17 @@ mod tests{
         json_types::ValidAccountId,
     };
 
-    fn env_setup(){
+    pub fn env_setup(){
+        env_setup_caller("stiltztinkerstein");
+    }
+
+    // predecessor is the account calling the contract
+    pub fn env_setup_caller(predecessor: &str){
+        env_setup_at(predecessor, 0);
+    }
+
+    // same as env_setup_caller, in a block with this timestamp
+    pub fn env_setup_at(predecessor: &str, block_timestamp: u64){
         let mut builder: VMContextBuilder = VMContextBuilder::new();
 
         // attributes we can set with the builder:
@@ -185,6 +249,12 @@ mod tests{
             ).unwrap()
         );
 
+        builder.predecessor_account_id(
+            ValidAccountId::try_from(predecessor).unwrap()
+        );
+
+        builder.block_timestamp(block_timestamp);
+
         testing_env!(builder.build());
 
         assert_eq!(
@@ -211,20 +281,23 @@ mod tests{
         let contract = Contract::default();
 
         // string_match_example(&self, arg: String)
-        assert_eq!(contract.string_match_example(a1), 1);
-        assert_eq!(contract.string_match_example(a2), 2);
-        assert_eq!(contract.string_match_example(a3), 3);
-        assert_eq!(contract.string_match_example(a4), 4);
-        assert_eq!(contract.string_match_example(a5), 5);
+        assert_eq!(contract.string_match_example(a1), Ok(1));
+        assert_eq!(contract.string_match_example(a2), Ok(2));
+        assert_eq!(contract.string_match_example(a3), Ok(3));
+        assert_eq!(contract.string_match_example(a4), Ok(4));
+        assert_eq!(contract.string_match_example(a5), Ok(5));
     }
 
     /// Test an error using argument with 0
     #[test]
-    #[should_panic(expected="Received 0. Must be 1, 2, 3, 4 or 5.")]
     fn string_match_example_error(){
         env_setup();
         let contract = Contract::default();
 
-        contract.string_match_example(String::from("0"));
+        let result = contract.string_match_example(String::from("0"));
+
+        assert_eq!(result, Err(Example0Error::OutOfRange(0)));
+        assert_eq!(result.unwrap_err().to_string(), "Received 0. Must be 1, 2, 3, 4 or 5.");
+        assert_eq!(contract.string_match_example(String::from("one")), Err(Example0Error::NotANumber(String::from("one"))));
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,87 @@
+//! State written by the first version of this lesson, before passwords were hashed and users were stored by id.
+//!
+//! The contract had a single `example_2_user`. Admin and Employee stored `pass: String`, Employee stored its
+//! permissions as `Vec<String>`, Admin had no permissions (it could do anything), and Client stored
+//! `orders: Vec<String>`. Borsh doesn't store field types, so the old bytes can't be read as the new Example2User.
+//! These are copies of the old types, used only by `Contract::migrate_plaintext_passwords` to read the old state,
+//! hash the password and move the user to the registry.
+//!
+//! The old orders were records of finished transactions, they become Delivered orders. The old free text
+//! actions, and the free text permissions that aren't a Permission, go to the audit log.
+//!
+
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+
+use crate::model::{Employee, Example0, Example1, Example2User};
+use crate::order::{Order, OrderStatus};
+use crate::password::Password;
+use crate::permission::Permissions;
+
+
+/// Employee, with a plaintext password and free text permissions.
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct PlaintextEmployee {
+    pub name: String,
+    pub id: u32,
+    pub pass: String,
+    pub permissions: Vec<String>,
+    pub actions: Vec<String>,
+}
+
+/// Example2User, with plaintext passwords.
+#[derive(BorshDeserialize, BorshSerialize)]
+pub enum PlaintextUser {
+    Admin{ name: String, id: u32, pass: String, actions: Vec<String> },
+    Client{ name: String, id: u32, orders: Vec<String> },
+    Employee( PlaintextEmployee ),
+}
+
+/// The contract state, with plaintext passwords.
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct PlaintextContract {
+    pub example_0: Example0,
+    pub example_1: Example1,
+    pub example_2_user: PlaintextUser,
+}
+
+
+impl PlaintextUser {
+    /// Hashes the password, converts the permissions and the orders, everything else stays the same.
+    ///
+    /// Admin gets every permission, since it used to be allowed to do anything.
+    ///
+    /// Also returns what goes to the audit log: the free text actions of Admin and Employee,
+    /// and the permissions of Employee that aren't a Permission.
+    pub fn into_user(self) -> (Example2User, Vec<String>) {
+        match self {
+            PlaintextUser::Admin { name, id, pass, actions } => (Example2User::Admin {
+                name,
+                id,
+                pass: Password::new(&pass),
+                permissions: Permissions::all(),
+            }, actions),
+            PlaintextUser::Client { name, id, orders } => (Example2User::Client {
+                name,
+                id,
+                orders: orders
+                    .into_iter()
+                    .enumerate()
+                    .map(|(order_id, description)| Order { id: order_id as u32, description, status: OrderStatus::Delivered })
+                    .collect(),
+            }, Vec::new()),
+            PlaintextUser::Employee(employee) => {
+                let (permissions, unknown) = Permissions::from_legacy(&employee.permissions);
+
+                let mut descriptions: Vec<String> = employee.actions;
+                descriptions.extend(unknown.into_iter().map(|permission| format!("Permission not converted: {}", permission)));
+
+                (Example2User::Employee(Employee {
+                    name: employee.name,
+                    id: employee.id,
+                    pass: Password::new(&employee.pass),
+                    permissions,
+                }), descriptions)
+            },
+        }
+    }
+}
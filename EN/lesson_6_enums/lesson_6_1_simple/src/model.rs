@@ -1,24 +1,22 @@
+use std::fmt;
+use std::str::FromStr;
+
 use near_sdk::{
     borsh::{self, BorshDeserialize, BorshSerialize},
     serde::{ Serialize, Deserialize },
 };    
 
 
-// As duas funções a seguir são declaradas para
-// termos duas implementações diferentes de uma mesma função "log".
-// As mensagens chamadas com essa função log aparecerão 
-// em testes e na máquina virtual.
+// contract_log! aparece em testes (println!) e na máquina virtual (env::log).
+// Mensagens "trace" são removidas na compilação quando um nível mínimo é escolhido
+// com as features do crate contract_log.
+use contract_log::contract_log;
 
+use named_variants_derive::NamedVariants;
 
-#[cfg(test)]
-pub fn log(message: &str){
-    println!("{}", message);
-}
-
-#[cfg(not(test))]
-pub fn log(message: &str){
-    near_sdk::env::log(message.as_bytes());
-}
+use crate::order::{Order, OrderError, OrderStatus};
+use crate::password::Password;
+use crate::permission::{Permission, Permissions};
 
 
 /// Um struct possui varios valores diferentes simultâneos, um enum só pode possuir um valor.
@@ -31,7 +29,9 @@ pub fn log(message: &str){
 ///  - Example0::FOURTH
 ///  - Example0::FIFTH
 /// 
-#[derive(BorshDeserialize, BorshSerialize, Clone, Serialize, Deserialize)]
+/// The variants are in order: Ord compares them by position, First < Second < ... < Fifth.
+/// 
+#[derive(BorshDeserialize, BorshSerialize, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 #[serde(crate = "near_sdk::serde")]
 pub enum Example0{
     First,
@@ -50,13 +50,39 @@ impl Default for Example0{
 
 // Similar to structs, let's implement some function on our enum Example0
 impl Example0{
+    /// Every variant, in order.
+    pub const ALL: [Example0; 5] = [
+        Example0::First,
+        Example0::Second,
+        Example0::Third,
+        Example0::Fourth,
+        Example0::Fifth,
+    ];
+
+    /// Iterates over every variant, from First to Fifth.
+    pub fn iter() -> impl Iterator<Item = Example0> {
+        Example0::ALL.iter().copied()
+    }
+
+    /// The variant after this one. After Fifth comes First.
+    pub fn next(&self) -> Example0 {
+        // get_number is the position of the next one in ALL
+        Example0::ALL[self.get_number() as usize % Example0::ALL.len()]
+    }
+
+    /// The variant before this one. Before First comes Fifth.
+    pub fn prev(&self) -> Example0 {
+        let length: usize = Example0::ALL.len();
+
+        Example0::ALL[(self.get_number() as usize + length - 2) % length]
+    }
 
     /// Check its own value and returns a number from 1..5.
     /// 
     /// We use a reference &self, meaning we only access the value, not modify it
     /// 
     pub fn get_number(&self) -> u32 {
-        log("Calling Example0::get_number");
+        contract_log!(trace, "Calling Example0::get_number");
 
         // and here we match enum choices/options to return values
         match self {
@@ -71,7 +97,7 @@ impl Example0{
     /// true if own enum value is set to Example0::Third
     pub fn is_third(&self) -> bool {
 
-        log("Calling Example0::is_third");
+        contract_log!(trace, "Calling Example0::is_third");
 
         // match compares value in the order specified, 
         // and if assign a variable to a match, that variable will have the return value
@@ -95,6 +121,58 @@ impl Example0{
 }
 
 
+/// Converts 1..5 to an Example0, the opposite of get_number.
+impl TryFrom<u32> for Example0 {
+    type Error = Example0Error;
+
+    fn try_from(number: u32) -> Result<Self, Self::Error> {
+        // Any number not matching the patterns above will be other
+        match number {
+            1 => Ok(Example0::First),
+            2 => Ok(Example0::Second),
+            3 => Ok(Example0::Third),
+            4 => Ok(Example0::Fourth),
+            5 => Ok(Example0::Fifth),
+            other => Err(Example0Error::OutOfRange(other)),
+        }
+    }
+}
+
+/// Parses "1".."5" to an Example0.
+impl FromStr for Example0 {
+    type Err = Example0Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().parse::<u32>() {
+            Ok(number) => Example0::try_from(number),
+            Err(_) => Err(Example0Error::NotANumber(value.to_string())),
+        }
+    }
+}
+
+
+/// Why a number or string isn't an Example0.
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub enum Example0Error {
+    /// The number isn't 1, 2, 3, 4 or 5.
+    OutOfRange(u32),
+    /// The string isn't a number.
+    NotANumber(String),
+}
+
+impl fmt::Display for Example0Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Example0Error::OutOfRange(number) => write!(f, "Received {}. Must be 1, 2, 3, 4 or 5.", number),
+            Example0Error::NotANumber(value) => write!(f, "Received \"{}\". Must be a number from 1 to 5.", value),
+        }
+    }
+}
+
+impl std::error::Error for Example0Error {}
+
+
 /// An enum allows different typs to be grouped into a single concept or entity
 /// 
 /// This example shows that using enums for complex types (composability) is not really a good idea.
@@ -104,7 +182,7 @@ impl Example0{
 /// 
 /// Use enums to group different types that seem to share similar concepts.
 /// 
-#[derive(Clone, BorshDeserialize, BorshSerialize, Serialize, Deserialize)]
+#[derive(Clone, BorshDeserialize, BorshSerialize, Debug, PartialEq, Serialize, Deserialize)]
 #[serde(crate = "near_sdk::serde")]
 pub enum Example1{
     NoValue,
@@ -115,6 +193,20 @@ pub enum Example1{
     ACLikeStruct{first: u32, second: String},
 }
 
+/// The variant of an Example1, without its value.
+/// 
+/// Used to tell which type a stored value has, e.g. when get_i32 finds a String.
+#[derive(BorshDeserialize, BorshSerialize, Clone, Copy, Debug, Deserialize, NamedVariants, PartialEq, Eq, Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub enum Example1Kind {
+    NoValue,
+    AnInteger,
+    AFloat,
+    AString,
+    ATuple,
+    ACLikeStruct,
+}
+
 impl Default for Example1{
     fn default() -> Self {
         Example1::NoValue
@@ -138,7 +230,7 @@ impl Example1{
 
     // Our function will return just a String type 
     pub fn get(&self) -> String {
-        log("Calling Example1::get");
+        contract_log!(trace, "Calling Example1::get");
 
         match self{
             Example1::NoValue => String::from(""),
@@ -150,9 +242,21 @@ impl Example1{
         }
     }
 
+    /// Returns the variant, without the value.
+    pub fn kind(&self) -> Example1Kind {
+        match self {
+            Example1::NoValue => Example1Kind::NoValue,
+            Example1::AnInteger(_) => Example1Kind::AnInteger,
+            Example1::AFloat(_) => Example1Kind::AFloat,
+            Example1::AString(_) => Example1Kind::AString,
+            Example1::ATuple(..) => Example1Kind::ATuple,
+            Example1::ACLikeStruct { .. } => Example1Kind::ACLikeStruct,
+        }
+    }
+
     /// true if enum is Example1::NoValue.
     pub fn is_no_value(&self) -> bool{
-        log("Calling Example1::is_no_value");
+        contract_log!(trace, "Calling Example1::is_no_value");
 
         match self{
             Example1::NoValue => true,
@@ -170,7 +274,7 @@ impl Example1{
     /// Option could be Option::Some(value) or Option::None.
     /// There's no NULL in Rust!
     pub fn get_an_integer(&self) -> Option<i32>{
-        log("Calling Example1::get_an_integer");
+        contract_log!(trace, "Calling Example1::get_an_integer");
 
         // value is a reference, so we clone the value to return an actual value and not a reference.
         match self{
@@ -182,7 +286,7 @@ impl Example1{
 
     /// Returns true if the enum represents an odd number
     pub fn has_an_odd_number(&self) -> bool {
-        log("Calling Example1::has_an_odd_number");
+        contract_log!(trace, "Calling Example1::has_an_odd_number");
 
         match self {
             Example1::NoValue => false,
@@ -214,27 +318,24 @@ impl Example1{
 pub struct Employee{
     pub name: String,
     pub id: u32,
-    pub pass: String,
-    pub permissions: Vec<String>,
-    pub actions: Vec<String>,
+    // only a salted digest is stored, and it's never serialized
+    #[serde(skip_serializing)]
+    pub pass: Password,
+    pub permissions: Permissions,
 }
 
 impl Default for Employee{
     fn default() -> Self {
-        log("Calling Employee::default");
+        contract_log!(trace, "Calling Employee::default");
 
         Employee { 
             name: String::from("a name"), 
             id: 11, 
-            pass: String::from("some random pass"), 
-            permissions: vec![
-                String::from("Can access google"),
-                format!("Can access 9gag"),
-            ], 
-            actions: vec![
-                String::from("Did something"),
-                String::from("Did something else"),
-            ],
+            pass: Password::new("some random pass"), 
+            permissions: Permissions::from(vec![
+                Permission::AccessClientLogs,
+                Permission::AccessStock,
+            ]), 
         }
     }
 }
@@ -249,21 +350,40 @@ impl Default for Employee{
 /// We cantrol permission of each of them using enums
 /// 
 /// All have name and id, and each has in addition:
-///  - Admin: password (encrypted) and  a list of action they can do in the app. 
-///  - Employee: password (encrypted), a list of actions they can do and a list of permissions for the app.
-///  - Client: only list of orders.
+///  - Admin: password (a salted hash) and their permissions.
+///  - Employee: password (a salted hash) and their permissions.
+///  - Client: only list of orders. Clients never have permissions.
+/// 
+/// Orders are in the module order.
 /// 
 #[derive(BorshDeserialize, BorshSerialize, Clone, Serialize, Deserialize)]
 #[serde(crate = "near_sdk::serde")]
 pub enum Example2User{
-    Admin{ name: String, id: u32, pass: String, actions: Vec<String> },
-    Client{ name: String, id: u32, orders: Vec<String> },
+    Admin{
+        name: String,
+        id: u32,
+        #[serde(skip_serializing)]
+        pass: Password,
+        permissions: Permissions,
+    },
+    Client{ name: String, id: u32, orders: Vec<Order> },
     Employee( Employee ),
 }
 
+/// The variant of an Example2User, without its values.
+/// 
+/// Used to filter users, e.g. list_users with role_filter "employee".
+#[derive(BorshDeserialize, BorshSerialize, Clone, Copy, Debug, Deserialize, NamedVariants, PartialEq, Eq, Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub enum Role {
+    Admin,
+    Client,
+    Employee,
+}
+
 impl Default for Example2User{
     fn default() -> Self {
-        log("Calling Example2User::default");
+        contract_log!(trace, "Calling Example2User::default");
 
         Example2User::Employee(Employee::default())
     }
@@ -275,67 +395,186 @@ impl Example2User{
     /// 
     /// The caller doesn't need to know the type of the user.
     pub fn get_name(&self) -> String {
-        log("Calling Example2User::get_name");
+        contract_log!(trace, "Calling Example2User::get_name");
 
         match self {
-            Example2User::Admin { name, id: _, pass: _, actions: _ } => { name.clone() },
+            Example2User::Admin { name, .. } => { name.clone() },
             Example2User::Client { name, id: _, orders: _ } => { name.clone() },
             Example2User::Employee( employee ) => { employee.name.clone() },
         }
     }
 
-    /// Check if an user has a permission.
+    /// Returns the role of the user.
+    pub fn role(&self) -> Role {
+        match self {
+            Example2User::Admin { .. } => Role::Admin,
+            Example2User::Client { .. } => Role::Client,
+            Example2User::Employee( _ ) => Role::Employee,
+        }
+    }
+
+    /// Replaces the user name.
+    pub fn set_name(&mut self, new_name: String) {
+        contract_log!(trace, "Calling Example2User::set_name");
+
+        match self {
+            Example2User::Admin { name, .. } => { *name = new_name },
+            Example2User::Client { name, .. } => { *name = new_name },
+            Example2User::Employee( employee ) => { employee.name = new_name },
+        }
+    }
+
+    /// Returns the user id
+    pub fn get_id(&self) -> u32 {
+        contract_log!(trace, "Calling Example2User::get_id");
+
+        match self {
+            Example2User::Admin { id, .. } => { *id },
+            Example2User::Client { id, .. } => { *id },
+            Example2User::Employee( employee ) => { employee.id },
+        }
+    }
+
+    /// Returns true if candidate is the password of Admin or Employee.
     /// 
-    /// Having Strings to keep permissions is not a good idea, due to possible errors. A better choice would be enums
+    /// Clients don't have passwords. Always returns false.
+    pub fn verify_password(&self, candidate: &str) -> bool {
+        contract_log!(trace, "Calling Example2User::verify_password");
+
+        match self {
+            Example2User::Client { .. } => false,
+            Example2User::Admin { pass, .. } => pass.verify(candidate),
+            Example2User::Employee( employee ) => employee.pass.verify(candidate),
+        }
+    }
+
+    /// Replaces the password of Admin or Employee, if current is the password.
+    /// 
+    /// Returns an error for Client, or if current is wrong.
+    pub fn change_password(&mut self, current: &str, new: &str) -> Result<(), String> {
+        contract_log!(trace, "Calling Example2User::change_password");
+
+        let pass: &mut Password = match self {
+            Example2User::Client { .. } => return Err(String::from("User is Client")),
+            Example2User::Admin { pass, .. } => pass,
+            Example2User::Employee( employee ) => &mut employee.pass,
+        };
+
+        if !pass.verify(current) {
+            return Err(String::from("Wrong password"));
+        }
+
+        *pass = Password::new(new);
+
+        Ok(())
+    }
+
+    /// Check if an user has a permission.
     /// 
     ///  - Clientes don't have permissions. Always return false.
-    ///  - Administrators have all permissions (can do anything). Always returns true.
-    ///  - Employees might have the permission. 
+    ///  - Administrators and Employees have the permissions they were granted.
     /// 
-    pub fn has_permission(&self, permission: String) -> bool{
-        log("Calling Example2User::has_permission");
+    pub fn has_permission(&self, permission: Permission) -> bool{
+        contract_log!(trace, "Calling Example2User::has_permission");
+
+        match self.permissions() {
+            Some(permissions) => permissions.contains(permission),
+            None => false,
+        }
+    }
 
+    /// Permissions of Admin or Employee. None for Client.
+    pub fn permissions(&self) -> Option<&Permissions> {
         match self{
-            Example2User::Client { name: _, id: _, orders: _ } => { false },
-            Example2User::Admin { name: _, id: _, pass: _, actions: _ } => { true },
-            Example2User::Employee(employee) => {
-
-                // Vec implement the Iterator trait, 
-                // so we have the iter() function available.
-                // This functions allows to iterate over references to strings,
-                // but no copies are made while iterating 
-                for employee_permission in employee.permissions.iter(){
-                    if permission == *employee_permission {
-                        return true;
-                    }
-                }
+            Example2User::Client { .. } => None,
+            Example2User::Admin { permissions, .. } => Some(permissions),
+            Example2User::Employee( employee ) => Some(&employee.permissions),
+        }
+    }
 
-                false
-            }
+    /// Gives a permission to Admin or Employee.
+    /// 
+    /// Returns an error for Client, or if the user already has it.
+    pub fn grant_permission(&mut self, permission: Permission) -> Result<(), String> {
+        contract_log!(trace, "Calling Example2User::grant_permission");
+
+        let permissions = self.permissions_mut()?;
+
+        if !permissions.insert(permission) {
+            return Err(format!("User already has permission {}", permission));
         }
+
+        Ok(())
     }
 
-    /// Returns a list of actions for Admin or Employee. 
+    /// Takes a permission from Admin or Employee.
     /// 
-    /// Result is similar to Option, but is used to provide more detail into the outcome while also accounting for errors
-    pub fn get_actions(&self) -> Result<Vec<String>, String> {
-        log("Calling Example2User::get_actions");
-        
-        // If user is Client, return error Err()
-        // If user is Admin or Employee, return reference to Vec.
-        let actions = match self{
-            Example2User::Client { name: _, id: _, orders: _ } => { return Err(format!("User is Client")); },
-            Example2User::Admin { name: _, id: _, pass: _, actions, } => { actions },
-            Example2User::Employee( employee ) => { &employee.actions },
-        };
+    /// Returns an error for Client, or if the user doesn't have it.
+    pub fn revoke_permission(&mut self, permission: Permission) -> Result<(), String> {
+        contract_log!(trace, "Calling Example2User::revoke_permission");
+
+        let permissions = self.permissions_mut()?;
 
-        let mut result: Vec<String> = Vec::new();
-        // Create a copy of vec, using references
-        for action in actions{
-            result.push(action.clone());
+        if !permissions.remove(permission) {
+            return Err(format!("User doesn't have permission {}", permission));
         }
 
-        Ok(result)
+        Ok(())
+    }
+
+    fn permissions_mut(&mut self) -> Result<&mut Permissions, String> {
+        match self{
+            Example2User::Client { .. } => Err(String::from("User is Client")),
+            Example2User::Admin { permissions, .. } => Ok(permissions),
+            Example2User::Employee( employee ) => Ok(&mut employee.permissions),
+        }
+    }
+
+    /// Orders of a Client.
+    /// 
+    /// Returns an error for Admin and Employee.
+    pub fn get_orders(&self) -> Result<&[Order], OrderError> {
+        contract_log!(trace, "Calling Example2User::get_orders");
+
+        match self {
+            Example2User::Client { orders, .. } => Ok(orders),
+            _ => Err(OrderError::NotAClient(self.get_id())),
+        }
+    }
+
+    /// Adds a Pending order to a Client. Returns the order id, its position in the list.
+    /// 
+    /// Returns an error for Admin and Employee.
+    pub fn place_order(&mut self, description: String) -> Result<u32, OrderError> {
+        contract_log!(trace, "Calling Example2User::place_order");
+
+        let orders: &mut Vec<Order> = self.orders_mut()?;
+        let order_id = orders.len() as u32;
+
+        orders.push(Order::new(order_id, description));
+
+        Ok(order_id)
+    }
+
+    /// Changes the status of an order of a Client.
+    /// 
+    /// Returns an error for Admin and Employee, if the order doesn't exist, or if the change isn't valid.
+    pub fn advance_order(&mut self, order_id: u32, status: OrderStatus) -> Result<(), OrderError> {
+        contract_log!(trace, "Calling Example2User::advance_order");
+
+        let client_id: u32 = self.get_id();
+
+        match self.orders_mut()?.get_mut(order_id as usize) {
+            Some(order) => order.advance(status),
+            None => Err(OrderError::OrderNotFound { client_id, order_id }),
+        }
+    }
+
+    fn orders_mut(&mut self) -> Result<&mut Vec<Order>, OrderError> {
+        match self {
+            Example2User::Client { orders, .. } => Ok(orders),
+            _ => Err(OrderError::NotAClient(self.get_id())),
+        }
     }
 }
 
@@ -345,6 +584,11 @@ mod tests{
     // use std::result;
 
     use super::*;
+    use near_sdk::{
+        MockedBlockchain,
+        testing_env,
+        test_utils::VMContextBuilder,
+    };
 
     #[test]
     fn example0() {
@@ -367,6 +611,39 @@ mod tests{
         assert_eq!(fifth.is_third(), false);
     }
 
+    #[test]
+    fn example0_ordinals() {
+        // TryFrom and get_number are opposites
+        for (position, variant) in Example0::iter().enumerate() {
+            assert_eq!(Example0::try_from(position as u32 + 1), Ok(variant));
+            assert_eq!(variant.get_number(), position as u32 + 1);
+        }
+
+        assert_eq!(Example0::try_from(0), Err(Example0Error::OutOfRange(0)));
+        assert_eq!(Example0::try_from(6), Err(Example0Error::OutOfRange(6)));
+
+        assert_eq!(" 3".parse::<Example0>(), Ok(Example0::Third));
+        assert_eq!("9".parse::<Example0>(), Err(Example0Error::OutOfRange(9)));
+        assert_eq!("third".parse::<Example0>(), Err(Example0Error::NotANumber(String::from("third"))));
+        assert_eq!("-1".parse::<Example0>(), Err(Example0Error::NotANumber(String::from("-1"))));
+
+        // next and prev go around
+        assert_eq!(Example0::First.next(), Example0::Second);
+        assert_eq!(Example0::Fifth.next(), Example0::First);
+        assert_eq!(Example0::First.prev(), Example0::Fifth);
+        assert_eq!(Example0::Third.prev(), Example0::Second);
+        for variant in Example0::iter() {
+            assert_eq!(variant.next().prev(), variant);
+        }
+
+        // Ord follows the declaration
+        assert!(Example0::First < Example0::Second);
+        assert_eq!(Example0::iter().max(), Some(Example0::Fifth));
+        let mut shuffled = vec![Example0::Fourth, Example0::First, Example0::Fifth, Example0::Third, Example0::Second];
+        shuffled.sort();
+        assert_eq!(shuffled, Example0::ALL.to_vec());
+    }
+
     // NoValue,
     // AnInteger(i32),
     // AFloat(f32),
@@ -423,7 +700,7 @@ mod tests{
         assert!(a_c_like_struct.eq_ignore_ascii_case(&format!("{{\nfirst: 1,\nsecond: \"second\",\n}}\n")));
     }
 
-    `   
+
     /// Check that only true is returned for no_value
     #[test]
     fn example1_is_novalue(){
@@ -488,41 +765,36 @@ mod tests{
         asserting(a_c_like_struct, None);
     }
 
-    // Admin{ name: String, id: u32, pass: String, actions: Vec<String> },
-    // Client{ name: String, id: u32, orders: Vec<String> },
+    // Admin{ name: String, id: u32, pass: Password, permissions: Permissions },
+    // Client{ name: String, id: u32, orders: Vec<Order> },
     // Employee( Employee ),
 
     /// Create 3 instances of Example2User for our tests
     fn example2_user_create() -> [Example2User; 3] {
+        // Password uses env::sha256 and env::random_seed
+        testing_env!(VMContextBuilder::new().build());
+
         [
             Example2User::Admin { 
                 name: String::from("Lucas"), 
                 id: 0, 
-                pass: String::from("12345"), 
-                actions: vec![
-                    String::from("Signed in 24 dec 2022, 06h33m49.67s"),
-                    String::from("Logged off 24 dec 2022, 09h22m01.18s"),
-                ]
+                pass: Password::new("12345"), 
+                permissions: Permissions::all(),
             },
             Example2User::Employee(Employee {
                  name: String::from("Lucas"), 
                  id: 1, 
-                 pass: String::from("123456"), 
-                 permissions: vec![
-                     String::from("Access client logs"),
-                     String::from("Access stock"),
-                 ], 
-                 actions : vec![
-                    String::from("Signed in 25 dec 2022, 08h11m32.01s"),
-                    format!("Accessed Logs from {} {} {} {}, {:02}h{:02}m{:02}.{:02}s", "Lucas", 25, "dec", 2022, 09, 45, 19, 05),
-                    String::from("Logged off 25 dec 2022, 11h44m51.92s"),
-                ]
+                 pass: Password::new("123456"), 
+                 permissions: Permissions::from(vec![
+                     Permission::AccessClientLogs,
+                     Permission::AccessStock,
+                 ]),
             }),
             Example2User::Client { 
                 name: String::from("Lucas"), 
                 id: 3, 
                 orders: Vec::from([
-                    format!("Successful transaction. ID: {}.", 4241235)
+                    Order::new(0, format!("Successful transaction. ID: {}.", 4241235))
                 ]) 
             }
         ]
@@ -555,85 +827,87 @@ mod tests{
 
         // Check if users have the log permission
         let (result_admin, result_employee, result_client) = (
-            admin.has_permission(String::from("Access client logs")),
-            employee.has_permission(String::from("Access client logs")),
-            client.has_permission(String::from("Access client logs")),
+            admin.has_permission(Permission::AccessClientLogs),
+            employee.has_permission(Permission::AccessClientLogs),
+            client.has_permission(Permission::AccessClientLogs),
         );
 
         assert_eq!(result_admin, true);
         assert_eq!(result_employee, true);
         assert_eq!(result_client, false);
+
+        // Employee wasn't given this one
+        assert_eq!(employee.has_permission(Permission::ManageUsers), false);
     }
 
     #[test]
-    fn example2_get_actions(){
-
-        // helper function to compare vectors
-        fn vec_eq(first: Result<Vec<String>, String>, second: Result<Vec<String>, String>) -> bool {
-            let (first, second) = match (first, second) {
-                (Err(first), Err(second)) => {
-                    return first == second;
-                },
-                (Ok(first), Ok(second)) => {
-                    (first.clone(), second.clone())
-                },
-                (_, _) => {
-                    return false;
-                }
-            };
+    fn example2_grant_and_revoke(){
+        let [mut admin, mut employee, mut client] = example2_user_create();
 
-            // vectors must have the same number of elements
-            assert_eq!(first.len(), second.len(), "len is different");
+        assert_eq!(employee.grant_permission(Permission::ManageOrders), Ok(()));
+        assert!(employee.has_permission(Permission::ManageOrders));
+        assert!(employee.grant_permission(Permission::ManageOrders).is_err());
 
-            let length: usize = first.len();
+        // Admins can lose permissions too
+        assert_eq!(admin.revoke_permission(Permission::ManageUsers), Ok(()));
+        assert!(!admin.has_permission(Permission::ManageUsers));
+        assert!(admin.revoke_permission(Permission::ManageUsers).is_err());
 
-            for counter in 0..length {
-                assert_eq!(first[counter], second[counter], "Failed comparison between {} and {}", first[counter], second[counter]);
-            }
+        assert_eq!(client.grant_permission(Permission::AccessStock), Err(String::from("User is Client")));
+        assert_eq!(client.revoke_permission(Permission::AccessStock), Err(String::from("User is Client")));
+    }
 
-            return true;
-        }
+    #[test]
+    fn example2_role_and_name(){
+        let [mut admin, employee, client] = example2_user_create();
 
-        // Create three users for testing below
-        let [admin, employee, client] = example2_user_create();
+        assert_eq!(admin.role(), Role::Admin);
+        assert_eq!(employee.role(), Role::Employee);
+        assert_eq!(client.role(), Role::Client);
+        assert_eq!("EMPLOYEE".parse::<Role>(), Ok(Role::Employee));
 
-        // get actions for each user
-        let (result_admin, result_employee, result_client) = (
-            admin.get_actions(),
-            employee.get_actions(),
-            client.get_actions(),
-        );
+        admin.set_name(String::from("Someone else"));
+        assert_eq!(admin.get_name(), "Someone else");
+        assert_eq!(admin.get_id(), 0);
+    }
 
-        // check admin actions
-        assert!(
-            vec_eq(
-                result_admin, 
-                Ok(vec![
-                    String::from("Signed in 24 dec 2022, 06h33m49.67s"),
-                    String::from("Logged off 24 dec 2022, 09h22m01.18s"),
-                ])
-            )
-        );
+    #[test]
+    fn example2_passwords(){
+        let [mut admin, employee, mut client] = example2_user_create();
+
+        assert!(admin.verify_password("12345"));
+        assert!(!admin.verify_password("123456"));
+        assert!(employee.verify_password("123456"));
+        assert!(!client.verify_password(""));
+
+        assert_eq!(admin.change_password("wrong", "54321"), Err(String::from("Wrong password")));
+        assert_eq!(admin.change_password("12345", "54321"), Ok(()));
+        assert!(admin.verify_password("54321"));
+        assert!(!admin.verify_password("12345"));
+
+        assert_eq!(client.change_password("", "a"), Err(String::from("User is Client")));
 
-        // check employee actions
-        assert!(
-            vec_eq(
-                result_employee,
-                Ok(vec![
-                    String::from("Signed in 25 dec 2022, 08h11m32.01s"),
-                    format!("Accessed Logs from {} {} {} {}, {:02}h{:02}m{:02}.{:02}s", "Lucas", 25, "dec", 2022, 09, 45, 19, 05),
-                    String::from("Logged off 25 dec 2022, 11h44m51.92s"),
-                ]),
-            )
+        // serde views never show the password
+        let json: String = near_sdk::serde_json::to_string(&[&admin, &employee]).unwrap();
+        assert!(!json.contains("pass"), "{}", json);
+    }
+
+    #[test]
+    fn example2_orders(){
+        let [mut admin, _, mut client] = example2_user_create();
+
+        assert_eq!(client.place_order(String::from("A keyboard")), Ok(1));
+        assert_eq!(client.advance_order(1, OrderStatus::Paid), Ok(()));
+        assert_eq!(client.advance_order(2, OrderStatus::Paid), Err(OrderError::OrderNotFound { client_id: 3, order_id: 2 }));
+        assert_eq!(
+            client.advance_order(1, OrderStatus::Cancelled),
+            Err(OrderError::InvalidTransition { order_id: 1, from: OrderStatus::Paid, to: OrderStatus::Cancelled }),
         );
 
-        // check client, and make sure it returns an error.
-        assert!(
-            vec_eq(
-                result_client,
-                Err(format!("User is Client")),
-            )
-        )
+        let statuses: Vec<OrderStatus> = client.get_orders().unwrap().iter().map(|order| order.status).collect();
+        assert_eq!(statuses, vec![OrderStatus::Pending, OrderStatus::Paid]);
 
+        assert_eq!(admin.place_order(String::from("A chair")), Err(OrderError::NotAClient(0)));
+        assert_eq!(admin.get_orders(), Err(OrderError::NotAClient(0)));
     }
 }
\ No newline at end of file
@@ -0,0 +1,367 @@
+//! Orders of Client users, and the contract functions that change them.
+//!
+//! The status of an order can only move forward, following these arrows:
+//!
+//! ```text
+//! Pending ──> Paid ──> Shipped ──> Delivered
+//!    │         │                       │
+//!    v         v                       v
+//! Cancelled  Refunded <────────────────┘
+//! ```
+//!
+//! Every rejected change returns an OrderError. Contract functions panic with it, tagged with an ID
+//! (`[invalid_transition] ...`), so callers and tests can tell the errors apart without reading the text.
+//!
+
+use std::fmt;
+
+use near_sdk::{
+    borsh::{self, BorshDeserialize, BorshSerialize},
+    env,
+    near_bindgen,
+    serde::{ Serialize, Deserialize },
+    AccountId,
+};
+
+use named_variants_derive::NamedVariants;
+
+use contract_log::contract_log;
+
+use crate::{
+    audit::Action,
+    log_call,
+    model::Example2User,
+    permission::Permission,
+    Contract,
+};
+
+
+/// Status of an order. See the module documentation for the valid changes.
+#[derive(BorshDeserialize, BorshSerialize, Clone, Copy, Debug, Deserialize, NamedVariants, PartialEq, Eq, Serialize)]
+#[named_variants(error = OrderError::InvalidStatus)]
+#[serde(crate = "near_sdk::serde")]
+pub enum OrderStatus {
+    Pending,
+    Paid,
+    Shipped,
+    Delivered,
+    Cancelled,
+    Refunded,
+}
+
+impl OrderStatus {
+    /// Returns true if an order with this status can change to next.
+    pub fn can_become(&self, next: OrderStatus) -> bool {
+        use OrderStatus::*;
+
+        matches!(
+            (self, next),
+            (Pending, Paid) | (Pending, Cancelled) |
+            (Paid, Shipped) | (Paid, Refunded) |
+            (Shipped, Delivered) |
+            (Delivered, Refunded)
+        )
+    }
+
+    /// Cancelled and Refunded orders can't change anymore.
+    pub fn is_final(&self) -> bool {
+        matches!(self, OrderStatus::Cancelled | OrderStatus::Refunded)
+    }
+}
+
+
+/// An order made by a Client.
+#[derive(BorshDeserialize, BorshSerialize, Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Order {
+    /// Position of the order in the list of its client
+    pub id: u32,
+    pub description: String,
+    pub status: OrderStatus,
+}
+
+impl Order {
+    /// A new order is Pending.
+    pub fn new(id: u32, description: String) -> Self {
+        Order {
+            id,
+            description,
+            status: OrderStatus::Pending,
+        }
+    }
+
+    /// Changes the status, if the change is valid.
+    pub fn advance(&mut self, next: OrderStatus) -> Result<(), OrderError> {
+        if !self.status.can_become(next) {
+            return Err(OrderError::InvalidTransition { order_id: self.id, from: self.status, to: next });
+        }
+
+        self.status = next;
+
+        Ok(())
+    }
+}
+
+
+/// Why an order couldn't be placed or changed.
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub enum OrderError {
+    /// The order can't change from this status to the other.
+    InvalidTransition { order_id: u32, from: OrderStatus, to: OrderStatus },
+    /// The client doesn't have an order with this id.
+    OrderNotFound { client_id: u32, order_id: u32 },
+    /// Only Clients have orders. Contains the user id.
+    NotAClient(u32),
+    /// The caller isn't linked to a Client.
+    CallerNotAClient(AccountId),
+    /// The name isn't a valid OrderStatus.
+    InvalidStatus(String),
+}
+
+impl OrderError {
+    /// Identifies the error in panic messages, e.g. "[invalid_transition] ..."
+    pub fn id(&self) -> &'static str {
+        match self {
+            OrderError::InvalidTransition { .. } => "invalid_transition",
+            OrderError::OrderNotFound { .. } => "order_not_found",
+            OrderError::NotAClient(_) => "not_a_client",
+            OrderError::CallerNotAClient(_) => "caller_not_a_client",
+            OrderError::InvalidStatus(_) => "invalid_status",
+        }
+    }
+}
+
+impl fmt::Display for OrderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OrderError::InvalidTransition { order_id, from, to } => write!(f, "Order {} can't change from {} to {}.", order_id, from, to),
+            OrderError::OrderNotFound { client_id, order_id } => write!(f, "Client {} doesn't have order {}.", client_id, order_id),
+            OrderError::NotAClient(user_id) => write!(f, "User {} is not a Client.", user_id),
+            OrderError::CallerNotAClient(account_id) => write!(f, "Account {} is not linked to a Client.", account_id),
+            OrderError::InvalidStatus(name) => write!(f, "Invalid name for OrderStatus ({}). Valid names: {:?}.", name, OrderStatus::ALL_VARIANTS),
+        }
+    }
+}
+
+impl std::error::Error for OrderError {}
+
+
+#[near_bindgen]
+impl Contract{
+    /// The caller's Client places an order. Returns the order id.
+    ///
+    /// # Panics
+    ///  - `[caller_not_a_client]` if the caller isn't linked to a Client.
+    pub fn place_order(&mut self, description: String) -> u32 {
+        log_call("place_order");
+
+        let (client_id, mut client) = self.caller_client();
+        let order_id: u32 = client.place_order(description).unwrap_or_else(|err| order_panic(err));
+
+        self.users.insert(&client_id, &client);
+
+        self.record(Action::OrderPlaced { client_id, order_id });
+        contract_log!(info, [order_placed] "Client {} placed order {}.", client_id, order_id);
+
+        order_id
+    }
+
+    /// The caller's Client cancels one of its orders. Only Pending orders can be cancelled.
+    ///
+    /// # Panics
+    ///  - `[caller_not_a_client]` if the caller isn't linked to a Client.
+    ///  - `[order_not_found]` if the client doesn't have this order.
+    ///  - `[invalid_transition]` if the order isn't Pending.
+    pub fn cancel_order(&mut self, order_id: u32) {
+        log_call("cancel_order");
+
+        let (client_id, mut client) = self.caller_client();
+
+        if let Err(err) = client.advance_order(order_id, OrderStatus::Cancelled) {
+            order_panic(err);
+        }
+
+        self.users.insert(&client_id, &client);
+
+        self.record(Action::OrderCancelled { client_id, order_id });
+        contract_log!(info, [order_cancelled] "Client {} cancelled order {}.", client_id, order_id);
+    }
+
+    /// Changes the status of an order of a Client. status is "Paid", "Shipped", "Delivered", "Cancelled" or "Refunded".
+    ///
+    /// Needs Permission::ManageOrders.
+    ///
+    /// # Panics
+    ///  - `[missing_permission]` if the caller doesn't have Permission::ManageOrders.
+    ///  - `[invalid_status]` if status isn't a valid name.
+    ///  - `[not_a_client]` if client_id isn't a Client.
+    ///  - `[order_not_found]` if the client doesn't have this order.
+    ///  - `[invalid_transition]` if the order can't change to this status.
+    pub fn advance_order(&mut self, client_id: u32, order_id: u32, status: String) {
+        log_call("advance_order");
+        self.require_permission(Permission::ManageOrders);
+
+        let status: OrderStatus = status.parse().unwrap_or_else(|err| order_panic(err));
+        let mut client: Example2User = self.stored_user(client_id);
+
+        if let Err(err) = client.advance_order(order_id, status) {
+            order_panic(err);
+        }
+
+        self.users.insert(&client_id, &client);
+
+        self.record(Action::OrderAdvanced { client_id, order_id, status });
+        contract_log!(info, [order_advanced] "Order {} of client {} is now {}.", order_id, client_id, status);
+    }
+
+    /// Orders of a Client.
+    ///
+    /// # Panics
+    ///  - If the user doesn't exist.
+    ///  - `[not_a_client]` if the user isn't a Client.
+    pub fn get_orders(&self, client_id: u32) -> Vec<Order> {
+        log_call("get_orders");
+
+        match self.stored_user(client_id).get_orders() {
+            Ok(orders) => orders.to_vec(),
+            Err(err) => order_panic(err),
+        }
+    }
+}
+
+
+impl Contract {
+    // The Client linked to the caller, and its id
+    fn caller_client(&self) -> (u32, Example2User) {
+        let predecessor: AccountId = env::predecessor_account_id();
+
+        match self.user_ids.get(&predecessor).and_then(|user_id| Some((user_id, self.users.get(&user_id)?))) {
+            Some((user_id, user @ Example2User::Client { .. })) => (user_id, user),
+            _ => order_panic(OrderError::CallerNotAClient(predecessor)),
+        }
+    }
+}
+
+
+// Panics with the error, tagged with its id
+fn order_panic(err: OrderError) -> ! {
+    panic!("[{}] {}", err.id(), err)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{env_setup, env_setup_caller};
+    use contract_log::{assert_logged, assert_panics};
+
+    #[test]
+    fn transitions() {
+        use OrderStatus::*;
+
+        let valid = [
+            (Pending, Paid), (Pending, Cancelled),
+            (Paid, Shipped), (Paid, Refunded),
+            (Shipped, Delivered),
+            (Delivered, Refunded),
+        ];
+
+        for from in OrderStatus::ALL_VARIANTS.iter().map(|name| name.parse::<OrderStatus>().unwrap()) {
+            for to in OrderStatus::ALL_VARIANTS.iter().map(|name| name.parse::<OrderStatus>().unwrap()) {
+                assert_eq!(from.can_become(to), valid.contains(&(from, to)), "{} -> {}", from, to);
+            }
+
+            if from.is_final() {
+                assert!(!valid.iter().any(|(valid_from, _)| *valid_from == from));
+            }
+        }
+    }
+
+    #[test]
+    fn advance() {
+        let mut order = Order::new(3, String::from("A keyboard"));
+
+        assert_eq!(
+            order.advance(OrderStatus::Shipped),
+            Err(OrderError::InvalidTransition { order_id: 3, from: OrderStatus::Pending, to: OrderStatus::Shipped }),
+        );
+        assert_eq!(order.status, OrderStatus::Pending);
+
+        assert_eq!(order.advance(OrderStatus::Paid), Ok(()));
+        assert_eq!(order.advance(OrderStatus::Shipped), Ok(()));
+        assert_eq!(order.advance(OrderStatus::Delivered), Ok(()));
+        assert_eq!(order.advance(OrderStatus::Refunded), Ok(()));
+        assert!(order.advance(OrderStatus::Paid).is_err());
+
+        assert_eq!("shipped".parse::<OrderStatus>(), Ok(OrderStatus::Shipped));
+        assert_eq!("lost".parse::<OrderStatus>(), Err(OrderError::InvalidStatus(String::from("lost"))));
+    }
+
+    // bob.near is a Client (id 1), alice.near an Employee (id 0) who can manage orders
+    fn contract_with_orders() -> Contract {
+        env_setup();
+        let mut contract = Contract::default();
+
+        contract.create_employee(String::from("alice.near"), String::from("Alice"), String::from("pass"), vec![String::from("orders")]);
+        contract.create_client(String::from("bob.near"), String::from("Bob"));
+        contract.create_employee(String::from("carol.near"), String::from("Carol"), String::from("pass"), vec![]);
+
+        contract
+    }
+
+    #[test]
+    fn order_lifecycle() {
+        let mut contract = contract_with_orders();
+
+        env_setup_caller("bob.near");
+        assert_eq!(contract.place_order(String::from("A keyboard")), 0);
+        assert_eq!(contract.place_order(String::from("A mouse")), 1);
+        assert_logged!(order_placed, [1, 0]);
+
+        contract.cancel_order(1);
+        assert_logged!(order_cancelled, [1, 1]);
+
+        env_setup_caller("alice.near");
+        contract.advance_order(1, 0, String::from("paid"));
+        contract.advance_order(1, 0, String::from("Shipped"));
+        assert_logged!(order_advanced, [0, 1, "Shipped"]);
+
+        let statuses: Vec<OrderStatus> = contract.get_orders(1).iter().map(|order| order.status).collect();
+        assert_eq!(statuses, vec![OrderStatus::Shipped, OrderStatus::Cancelled]);
+    }
+
+    #[test]
+    fn rejected_changes() {
+        let mut contract = contract_with_orders();
+
+        env_setup_caller("bob.near");
+        contract.place_order(String::from("A keyboard"));
+
+        // can't ship before it's paid
+        env_setup_caller("alice.near");
+        assert_panics!(invalid_transition, contract.advance_order(1, 0, String::from("shipped")), [0, "Pending", "Shipped"]);
+        assert_panics!(invalid_status, contract.advance_order(1, 0, String::from("lost")), ["lost"]);
+        assert_panics!(order_not_found, contract.advance_order(1, 7, String::from("paid")), [1, 7]);
+        assert_panics!(not_a_client, contract.advance_order(0, 0, String::from("paid")), [0]);
+
+        // clients can't cancel paid orders
+        contract.advance_order(1, 0, String::from("paid"));
+        env_setup_caller("bob.near");
+        assert_panics!(invalid_transition, contract.cancel_order(0), ["Paid", "Cancelled"]);
+
+        // only clients place orders
+        env_setup_caller("alice.near");
+        assert_panics!(caller_not_a_client, contract.place_order(String::from("A chair")), ["alice.near"]);
+        env_setup_caller("dave.near");
+        assert_panics!(caller_not_a_client, contract.cancel_order(0), ["dave.near"]);
+
+        // and only employees with ManageOrders advance them
+        env_setup_caller("carol.near");
+        assert_panics!(missing_permission, contract.advance_order(1, 0, String::from("shipped")));
+        env_setup_caller("bob.near");
+        assert_panics!(missing_permission, contract.advance_order(1, 0, String::from("shipped")));
+
+        assert_eq!(contract.get_orders(1)[0].status, OrderStatus::Paid);
+    }
+}
@@ -0,0 +1,130 @@
+//! Passwords of Admin and Employee users.
+//!
+//! The contract state is public: anyone can read it with a RPC call. So a password is never stored,
+//! only a salted sha256 digest of it. Checking a candidate password hashes it with the same salt,
+//! and compares the digests.
+//!
+//! The salt and the digest are public too, so this doesn't protect a weak password from being guessed offline.
+//!
+//! With serde, a Password is received as plaintext (e.g. the arguments of create_admin, create_employee
+//! and change_password) and hashed right away. It is never serialized, views skip the field.
+//!
+
+use std::sync::atomic::{AtomicU32, Ordering};
+
+use near_sdk::{
+    borsh::{self, BorshDeserialize, BorshSerialize},
+    env,
+    serde::{Deserialize, Deserializer},
+};
+
+
+// Users created in the same call get the same random_seed, the counter makes their salts different
+static SALT_COUNTER: AtomicU32 = AtomicU32::new(0);
+
+
+/// A salted sha256 digest of a password.
+#[derive(BorshDeserialize, BorshSerialize, Clone, PartialEq, Eq)]
+pub struct Password {
+    salt: [u8; 16],
+    hash: [u8; 32],
+}
+
+impl Password {
+    /// Hashes a password with a new salt.
+    pub fn new(plaintext: &str) -> Self {
+        let counter: u32 = SALT_COUNTER.fetch_add(1, Ordering::Relaxed);
+
+        let mut seed: Vec<u8> = env::random_seed();
+        seed.extend_from_slice(&counter.to_le_bytes());
+
+        let mut salt = [0u8; 16];
+        salt.copy_from_slice(&env::sha256(&seed)[..16]);
+
+        Password {
+            hash: digest(&salt, plaintext),
+            salt,
+        }
+    }
+
+    /// Returns true if candidate is the password.
+    pub fn verify(&self, candidate: &str) -> bool {
+        let candidate: [u8; 32] = digest(&self.salt, candidate);
+
+        // Compare every byte, so the time taken doesn't tell how many bytes matched
+        self.hash
+            .iter()
+            .zip(candidate.iter())
+            .fold(0u8, |difference, (a, b)| difference | (a ^ b)) == 0
+    }
+}
+
+
+// sha256(salt + password)
+fn digest(salt: &[u8; 16], plaintext: &str) -> [u8; 32] {
+    let mut input: Vec<u8> = salt.to_vec();
+    input.extend_from_slice(plaintext.as_bytes());
+
+    let mut hash = [0u8; 32];
+    hash.copy_from_slice(&env::sha256(&input));
+
+    hash
+}
+
+
+// JSON arguments carry the plaintext, which is hashed before it's stored
+impl<'de> Deserialize<'de> for Password {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let plaintext = <String as Deserialize>::deserialize(deserializer)?;
+
+        Ok(Password::new(&plaintext))
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use near_sdk::{
+        MockedBlockchain,
+        testing_env,
+        test_utils::VMContextBuilder,
+    };
+
+    #[test]
+    fn verify() {
+        testing_env!(VMContextBuilder::new().build());
+
+        let password = Password::new("12345");
+
+        assert!(password.verify("12345"));
+        assert!(!password.verify("123456"));
+        assert!(!password.verify(""));
+    }
+
+    #[test]
+    fn salted() {
+        testing_env!(VMContextBuilder::new().build());
+
+        let (first, second) = (Password::new("12345"), Password::new("12345"));
+
+        // Same password, different digests
+        assert_ne!(first.salt, second.salt);
+        assert_ne!(first.hash, second.hash);
+        assert!(second.verify("12345"));
+
+        // Nothing in the stored bytes is the password
+        let stored: Vec<u8> = first.try_to_vec().unwrap();
+        assert_eq!(stored.len(), 48);
+        assert!(!stored.windows(5).any(|window| window == b"12345"));
+    }
+
+    #[test]
+    fn deserialize_plaintext() {
+        testing_env!(VMContextBuilder::new().build());
+
+        let password: Password = near_sdk::serde_json::from_str("\"hunter2\"").unwrap();
+
+        assert!(password.verify("hunter2"));
+    }
+}
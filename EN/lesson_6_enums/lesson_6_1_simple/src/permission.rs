@@ -0,0 +1,233 @@
+//! Permissions of Example2User.
+//!
+//! Each Permission is a single bit, so a user's permissions are stored as one u32 (Permissions),
+//! instead of a list of Strings. A typo in a String is a permission that nobody has, a typo in an enum
+//! doesn't compile.
+//!
+//! ```
+//! use lesson_6_1_simple::permission::{Permission, Permissions};
+//!
+//! let mut permissions = Permissions::empty();
+//! permissions.insert(Permission::AccessStock);
+//! permissions.insert("orders".parse().unwrap());
+//!
+//! assert!(permissions.contains(Permission::ManageOrders));
+//! assert!(!permissions.contains(Permission::ManageUsers));
+//! assert_eq!(permissions.list(), vec![Permission::AccessStock, Permission::ManageOrders]);
+//! ```
+//!
+
+use near_sdk::{
+    borsh::{self, BorshDeserialize, BorshSerialize},
+    serde::{ Serialize, Deserialize },
+};
+
+use named_variants_derive::NamedVariants;
+
+
+/// Something a user is allowed to do.
+///
+/// The value of each variant is the position of its bit in Permissions. New permissions must be added
+/// at the end, or stored users would change permissions.
+///
+/// Names aren't case-sensitive, and each permission has a short alias: "ManageOrders" or "orders".
+#[derive(BorshDeserialize, BorshSerialize, Clone, Copy, Debug, Deserialize, NamedVariants, PartialEq, Eq, Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub enum Permission {
+    #[alias("client_logs")]
+    AccessClientLogs = 0,
+    #[alias("stock")]
+    AccessStock = 1,
+    #[alias("orders")]
+    ManageOrders = 2,
+    #[alias("users")]
+    ManageUsers = 3,
+}
+
+impl Permission {
+    /// Every permission, in the order of their bits.
+    pub const ALL: [Permission; 4] = [
+        Permission::AccessClientLogs,
+        Permission::AccessStock,
+        Permission::ManageOrders,
+        Permission::ManageUsers,
+    ];
+
+    /// The bit of this permission in Permissions.
+    pub const fn bit(self) -> u32 {
+        1 << self as u32
+    }
+}
+
+
+/// A set of permissions, stored as a bitset.
+///
+/// Borsh stores the u32 as is (4 bytes). Serde shows it as a list of permissions, so JSON stays readable:
+/// `["AccessStock", "ManageOrders"]`.
+#[derive(BorshDeserialize, BorshSerialize, Clone, Copy, Debug, Default, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(crate = "near_sdk::serde", from = "Vec<Permission>", into = "Vec<Permission>")]
+pub struct Permissions(u32);
+
+impl Permissions {
+    /// No permissions.
+    pub const fn empty() -> Self {
+        Permissions(0)
+    }
+
+    /// Every permission.
+    pub fn all() -> Self {
+        Permission::ALL.iter().copied().collect()
+    }
+
+    pub fn contains(&self, permission: Permission) -> bool {
+        self.0 & permission.bit() != 0
+    }
+
+    /// Adds a permission. Returns false if it was already there.
+    pub fn insert(&mut self, permission: Permission) -> bool {
+        let added = !self.contains(permission);
+        self.0 |= permission.bit();
+
+        added
+    }
+
+    /// Removes a permission. Returns false if it wasn't there.
+    pub fn remove(&mut self, permission: Permission) -> bool {
+        let removed = self.contains(permission);
+        self.0 &= !permission.bit();
+
+        removed
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// True if every permission in this set is also in other.
+    pub fn is_subset(&self, other: &Permissions) -> bool {
+        self.0 & !other.0 == 0
+    }
+
+    /// Permissions in this set, in the order of their bits.
+    pub fn list(&self) -> Vec<Permission> {
+        Permission::ALL.iter().copied().filter(|permission| self.contains(*permission)).collect()
+    }
+
+    /// Converts the free text permissions stored before Permission existed.
+    ///
+    /// A String is a permission if it is a name or alias, ignoring case and spaces: "Manage Orders" is ManageOrders.
+    /// Returns the permissions found, and every String that isn't a permission, like "Can access 9gag".
+    pub fn from_legacy(names: &[String]) -> (Permissions, Vec<String>) {
+        let mut permissions = Permissions::empty();
+        let mut unknown: Vec<String> = Vec::new();
+
+        for name in names {
+            let without_spaces: String = name.split_whitespace().collect();
+
+            match name.trim().parse().or_else(|_| without_spaces.parse()) {
+                Ok(permission) => { permissions.insert(permission); },
+                Err(_) => unknown.push(name.clone()),
+            }
+        }
+
+        (permissions, unknown)
+    }
+}
+
+impl FromIterator<Permission> for Permissions {
+    fn from_iter<T: IntoIterator<Item = Permission>>(iter: T) -> Self {
+        let mut permissions = Permissions::empty();
+
+        for permission in iter {
+            permissions.insert(permission);
+        }
+
+        permissions
+    }
+}
+
+impl From<Vec<Permission>> for Permissions {
+    fn from(permissions: Vec<Permission>) -> Self {
+        permissions.into_iter().collect()
+    }
+}
+
+impl From<Permissions> for Vec<Permission> {
+    fn from(permissions: Permissions) -> Self {
+        permissions.list()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn insert_and_remove() {
+        let mut permissions = Permissions::empty();
+
+        assert!(permissions.insert(Permission::ManageUsers));
+        assert!(!permissions.insert(Permission::ManageUsers));
+        assert!(permissions.contains(Permission::ManageUsers));
+        assert!(!permissions.contains(Permission::AccessStock));
+
+        assert!(permissions.remove(Permission::ManageUsers));
+        assert!(!permissions.remove(Permission::ManageUsers));
+        assert!(permissions.is_empty());
+
+        assert_eq!(Permissions::all().list(), Permission::ALL.to_vec());
+    }
+
+    #[test]
+    fn is_subset() {
+        let stock: Permissions = vec![Permission::AccessStock].into();
+        let stock_and_orders: Permissions = vec![Permission::AccessStock, Permission::ManageOrders].into();
+
+        assert!(stock.is_subset(&stock_and_orders));
+        assert!(!stock_and_orders.is_subset(&stock));
+        assert!(Permissions::empty().is_subset(&stock));
+        assert!(stock_and_orders.is_subset(&Permissions::all()));
+    }
+
+    #[test]
+    fn bits_are_unique() {
+        let mut seen: u32 = 0;
+
+        for permission in Permission::ALL {
+            assert_eq!(seen & permission.bit(), 0, "{} shares a bit", permission);
+            seen |= permission.bit();
+        }
+    }
+
+    #[test]
+    fn serialization() {
+        let permissions: Permissions = vec![Permission::AccessStock, Permission::ManageOrders].into();
+
+        // borsh keeps the bitset
+        assert_eq!(permissions.try_to_vec().unwrap(), vec![0b0110, 0, 0, 0]);
+
+        // serde shows the names
+        let json = near_sdk::serde_json::to_string(&permissions).unwrap();
+        assert_eq!(json, "[\"AccessStock\",\"ManageOrders\"]");
+        assert_eq!(near_sdk::serde_json::from_str::<Permissions>(&json).unwrap(), permissions);
+
+        assert_eq!("client_logs".parse::<Permission>(), Ok(Permission::AccessClientLogs));
+        assert!("Can access 9gag".parse::<Permission>().is_err());
+    }
+
+    #[test]
+    fn from_legacy() {
+        let names: Vec<String> = vec![
+            String::from("Can access google"),
+            String::from("Manage Orders"),
+            String::from(" stock "),
+            String::from("Can access 9gag"),
+        ];
+
+        let (permissions, unknown) = Permissions::from_legacy(&names);
+
+        assert_eq!(permissions.list(), vec![Permission::AccessStock, Permission::ManageOrders]);
+        assert_eq!(unknown, vec!["Can access google", "Can access 9gag"]);
+    }
+}
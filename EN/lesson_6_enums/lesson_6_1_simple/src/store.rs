@@ -0,0 +1,498 @@
+//! A keyed store of Example1 values, for configuration that doesn't need a schema.
+//!
+//! Each key holds any variant of Example1. The typed getters (get_i32, get_string, ...) check the
+//! variant and return a StoreError when it's another one. get_kind tells the variant beforehand. Contract functions panic with it, tagged
+//! with an ID (`[type_mismatch] ...`).
+//!
+//! Values are read and written as JSON, in one of two encodings:
+//!
+//! | Example1                                  | "tagged"                                           | "untagged"                         |
+//! |-------------------------------------------|----------------------------------------------------|------------------------------------|
+//! | NoValue                                   | `"NoValue"`                                        | `null`                             |
+//! | AnInteger(5)                              | `{"AnInteger": 5}`                                 | `5`                                |
+//! | AFloat(1.5)                               | `{"AFloat": 1.5}`                                  | `1.5`                              |
+//! | AString("on")                             | `{"AString": "on"}`                                | `"on"`                             |
+//! | ATuple(-1, 2)                             | `{"ATuple": [-1, 2]}`                              | `[-1, 2]`                          |
+//! | ACLikeStruct{first: 1, second: "a"}       | `{"ACLikeStruct": {"first": 1, "second": "a"}}`    | `{"first": 1, "second": "a"}`      |
+//!
+//! Untagged is shorter, but the variant is guessed from the JSON type, in the order of the table.
+//! Whole numbers that fit an i32 become AnInteger, any other number becomes AFloat.
+//!
+
+use std::fmt;
+use std::ops::Bound;
+
+use near_sdk::{
+    near_bindgen,
+    serde::{ Serialize, Deserialize },
+    serde_json::{self, Value},
+};
+
+use named_variants_derive::NamedVariants;
+
+use contract_log::contract_log;
+
+use crate::{
+    audit::Action,
+    log_call,
+    model::{Example1, Example1Kind},
+    users::{DEFAULT_LIST_LIMIT, MAX_LIST_LIMIT},
+    Contract,
+};
+
+
+/// How a value is written in JSON. See the module documentation.
+#[derive(Clone, Copy, Debug, Deserialize, NamedVariants, PartialEq, Eq, Serialize)]
+#[named_variants(error = StoreError::InvalidEncoding)]
+#[serde(crate = "near_sdk::serde")]
+pub enum Encoding {
+    Tagged,
+    Untagged,
+}
+
+impl Encoding {
+    pub fn encode(&self, value: &Example1) -> Value {
+        let encoded = match self {
+            Encoding::Tagged => serde_json::to_value(value),
+            Encoding::Untagged => serde_json::to_value(Untagged::from(value.clone())),
+        };
+
+        encoded.expect("Example1 is always valid JSON")
+    }
+
+    pub fn decode(&self, value: Value) -> Result<Example1, StoreError> {
+        let decoded = match self {
+            Encoding::Tagged => serde_json::from_value(value),
+            Encoding::Untagged => serde_json::from_value::<Untagged>(value).map(Example1::from),
+        };
+
+        decoded.map_err(|err| StoreError::InvalidValue { encoding: *self, reason: err.to_string() })
+    }
+}
+
+
+// Same variants as Example1, serde picks the first one that fits the JSON
+#[derive(Deserialize, Serialize)]
+#[serde(crate = "near_sdk::serde", untagged)]
+enum Untagged {
+    NoValue,
+    AnInteger(i32),
+    AFloat(f32),
+    AString(String),
+    ATuple(i32, u32),
+    ACLikeStruct{first: u32, second: String},
+}
+
+impl From<Example1> for Untagged {
+    fn from(value: Example1) -> Self {
+        match value {
+            Example1::NoValue => Untagged::NoValue,
+            Example1::AnInteger(value) => Untagged::AnInteger(value),
+            Example1::AFloat(value) => Untagged::AFloat(value),
+            Example1::AString(value) => Untagged::AString(value),
+            Example1::ATuple(first, second) => Untagged::ATuple(first, second),
+            Example1::ACLikeStruct { first, second } => Untagged::ACLikeStruct { first, second },
+        }
+    }
+}
+
+impl From<Untagged> for Example1 {
+    fn from(value: Untagged) -> Self {
+        match value {
+            Untagged::NoValue => Example1::NoValue,
+            Untagged::AnInteger(value) => Example1::AnInteger(value),
+            Untagged::AFloat(value) => Example1::AFloat(value),
+            Untagged::AString(value) => Example1::AString(value),
+            Untagged::ATuple(first, second) => Example1::ATuple(first, second),
+            Untagged::ACLikeStruct { first, second } => Example1::ACLikeStruct { first, second },
+        }
+    }
+}
+
+
+/// Why a value couldn't be read or written.
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub enum StoreError {
+    /// Nothing is stored with this key.
+    NotFound(String),
+    /// The value stored with the key is another variant.
+    TypeMismatch { key: String, expected: Example1Kind, found: Example1Kind },
+    /// The JSON isn't an Example1 in this encoding.
+    InvalidValue { encoding: Encoding, reason: String },
+    /// The name isn't a valid Encoding.
+    InvalidEncoding(String),
+}
+
+impl StoreError {
+    /// Identifies the error in panic messages, e.g. "[type_mismatch] ..."
+    pub fn id(&self) -> &'static str {
+        match self {
+            StoreError::NotFound(_) => "value_not_found",
+            StoreError::TypeMismatch { .. } => "type_mismatch",
+            StoreError::InvalidValue { .. } => "invalid_value",
+            StoreError::InvalidEncoding(_) => "invalid_encoding",
+        }
+    }
+}
+
+impl fmt::Display for StoreError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StoreError::NotFound(key) => write!(f, "No value with key \"{}\".", key),
+            StoreError::TypeMismatch { key, expected, found } => write!(f, "Value \"{}\" is {}, not {}.", key, found, expected),
+            StoreError::InvalidValue { encoding, reason } => write!(f, "Invalid {} value: {}", encoding, reason),
+            StoreError::InvalidEncoding(name) => write!(f, "Invalid name for Encoding ({}). Valid names: {:?}.", name, Encoding::ALL_VARIANTS),
+        }
+    }
+}
+
+impl std::error::Error for StoreError {}
+
+
+#[near_bindgen]
+impl Contract{
+    /// Stores a value with this key, replacing the old one.
+    ///
+    /// encoding is "tagged" (default) or "untagged".
+    ///
+    /// Only owner can call this function.
+    ///
+    /// # Panics
+    ///  - If caller is not owner.
+    ///  - `[invalid_encoding]` if encoding isn't a valid name.
+    ///  - `[invalid_value]` if value isn't an Example1 in this encoding.
+    pub fn put(&mut self, key: String, value: Value, encoding: Option<String>) {
+        log_call("put");
+        self.assert_owner_only();
+
+        let value: Example1 = unwrap_or_panic(parse_encoding(encoding).and_then(|encoding| encoding.decode(value)));
+
+        self.values.insert(&key, &value);
+
+        self.record(Action::ValuePut { key: key.clone(), kind: value.kind() });
+        contract_log!(info, [value_put] "Stored {} with key \"{}\".", value.kind(), key);
+    }
+
+    /// Removes the value with this key. Returns true if there was one.
+    ///
+    /// Only owner can call this function.
+    ///
+    /// # Panics
+    ///  - If caller is not owner.
+    pub fn remove(&mut self, key: String) -> bool {
+        log_call("remove");
+        self.assert_owner_only();
+
+        if self.values.remove(&key).is_none() {
+            return false;
+        }
+
+        self.record(Action::ValueRemoved { key: key.clone() });
+        contract_log!(info, [value_removed] "Removed key \"{}\".", key);
+
+        true
+    }
+
+    /// Returns the value with this key. encoding is "tagged" (default) or "untagged".
+    ///
+    /// # Panics
+    ///  - `[invalid_encoding]` if encoding isn't a valid name.
+    ///  - `[value_not_found]` if there's no value with this key.
+    pub fn get(&self, key: String, encoding: Option<String>) -> Value {
+        log_call("get");
+
+        let encoding: Encoding = unwrap_or_panic(parse_encoding(encoding));
+
+        encoding.encode(&unwrap_or_panic(self.stored_value(&key)))
+    }
+
+    /// Returns the AnInteger with this key.
+    ///
+    /// # Panics
+    ///  - `[value_not_found]` if there's no value with this key.
+    ///  - `[type_mismatch]` if the value is another variant.
+    pub fn get_i32(&self, key: String) -> i32 {
+        log_call("get_i32");
+
+        unwrap_or_panic(self.typed_value(key, Example1Kind::AnInteger, |value| match value {
+            Example1::AnInteger(value) => Some(value),
+            _ => None,
+        }))
+    }
+
+    /// Returns the AFloat with this key.
+    ///
+    /// # Panics
+    ///  - `[value_not_found]` if there's no value with this key.
+    ///  - `[type_mismatch]` if the value is another variant.
+    pub fn get_f32(&self, key: String) -> f32 {
+        log_call("get_f32");
+
+        unwrap_or_panic(self.typed_value(key, Example1Kind::AFloat, |value| match value {
+            Example1::AFloat(value) => Some(value),
+            _ => None,
+        }))
+    }
+
+    /// Returns the AString with this key.
+    ///
+    /// # Panics
+    ///  - `[value_not_found]` if there's no value with this key.
+    ///  - `[type_mismatch]` if the value is another variant.
+    pub fn get_string(&self, key: String) -> String {
+        log_call("get_string");
+
+        unwrap_or_panic(self.typed_value(key, Example1Kind::AString, |value| match value {
+            Example1::AString(value) => Some(value),
+            _ => None,
+        }))
+    }
+
+    /// Returns the ATuple with this key.
+    ///
+    /// # Panics
+    ///  - `[value_not_found]` if there's no value with this key.
+    ///  - `[type_mismatch]` if the value is another variant.
+    pub fn get_tuple(&self, key: String) -> (i32, u32) {
+        log_call("get_tuple");
+
+        unwrap_or_panic(self.typed_value(key, Example1Kind::ATuple, |value| match value {
+            Example1::ATuple(first, second) => Some((first, second)),
+            _ => None,
+        }))
+    }
+
+    /// Returns the ACLikeStruct with this key, as (first, second).
+    ///
+    /// # Panics
+    ///  - `[value_not_found]` if there's no value with this key.
+    ///  - `[type_mismatch]` if the value is another variant.
+    pub fn get_struct(&self, key: String) -> (u32, String) {
+        log_call("get_struct");
+
+        unwrap_or_panic(self.typed_value(key, Example1Kind::ACLikeStruct, |value| match value {
+            Example1::ACLikeStruct { first, second } => Some((first, second)),
+            _ => None,
+        }))
+    }
+
+    /// Returns the variant of the value with this key, so the right typed getter can be called.
+    ///
+    /// # Panics
+    ///  - `[value_not_found]` if there's no value with this key.
+    pub fn get_kind(&self, key: String) -> Example1Kind {
+        log_call("get_kind");
+
+        unwrap_or_panic(self.stored_value(&key)).kind()
+    }
+
+    /// Returns true if the value with this key is NoValue. NoValue has nothing to get, so it has no typed getter.
+    ///
+    /// # Panics
+    ///  - `[value_not_found]` if there's no value with this key.
+    pub fn is_no_value(&self, key: String) -> bool {
+        log_call("is_no_value");
+
+        unwrap_or_panic(self.stored_value(&key)).is_no_value()
+    }
+
+    /// Lists keys in alphabetical order.
+    ///
+    ///  - prefix: only keys starting with it, e.g. "ui.".
+    ///  - from: only keys after it.
+    ///  - limit: most keys returned. Default DEFAULT_LIST_LIMIT, at most MAX_LIST_LIMIT.
+    ///
+    /// To get the next page, call again with from set to the last key returned.
+    pub fn list_keys(&self, prefix: Option<String>, from: Option<String>, limit: Option<u32>) -> Vec<String> {
+        log_call("list_keys");
+
+        let limit: u32 = limit.unwrap_or(DEFAULT_LIST_LIMIT).min(MAX_LIST_LIMIT);
+        let prefix: String = prefix.unwrap_or_default();
+
+        // Start at the prefix, or after from if it comes later
+        let start: Bound<String> = match from {
+            Some(from) if from >= prefix => Bound::Excluded(from),
+            _ => Bound::Included(prefix.clone()),
+        };
+
+        self.values
+            .range((start, Bound::Unbounded))
+            .map(|(key, _)| key)
+            .take_while(|key| key.starts_with(&prefix))
+            .take(limit as usize)
+            .collect()
+    }
+}
+
+
+impl Contract {
+    fn stored_value(&self, key: &str) -> Result<Example1, StoreError> {
+        self.values.get(&key.to_string()).ok_or_else(|| StoreError::NotFound(key.to_string()))
+    }
+
+    // The value with this key, if extract accepts its variant
+    fn typed_value<T>(&self, key: String, expected: Example1Kind, extract: impl FnOnce(Example1) -> Option<T>) -> Result<T, StoreError> {
+        let value: Example1 = self.stored_value(&key)?;
+        let found: Example1Kind = value.kind();
+
+        extract(value).ok_or(StoreError::TypeMismatch { key, expected, found })
+    }
+}
+
+
+fn parse_encoding(encoding: Option<String>) -> Result<Encoding, StoreError> {
+    match encoding {
+        Some(encoding) => encoding.parse(),
+        None => Ok(Encoding::Tagged),
+    }
+}
+
+// Panics with the error, tagged with its id
+fn unwrap_or_panic<T>(result: Result<T, StoreError>) -> T {
+    match result {
+        Ok(value) => value,
+        Err(err) => panic!("[{}] {}", err.id(), err),
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{env_setup, env_setup_caller};
+    use contract_log::{assert_logged, assert_panics};
+    use near_sdk::serde_json::json;
+
+    fn all_variants() -> Vec<Example1> {
+        vec![
+            Example1::NoValue,
+            Example1::AnInteger(-5),
+            Example1::AFloat(1.5),
+            Example1::AString(String::from("on")),
+            Example1::ATuple(-1, 2),
+            Example1::ACLikeStruct { first: 1, second: String::from("a") },
+        ]
+    }
+
+    #[test]
+    fn encodings() {
+        let untagged: Vec<Value> = vec![json!(null), json!(-5), json!(1.5), json!("on"), json!([-1, 2]), json!({"first": 1, "second": "a"})];
+
+        for (value, untagged) in all_variants().into_iter().zip(untagged) {
+            assert_eq!(Encoding::Untagged.encode(&value), untagged);
+            assert_eq!(Encoding::Untagged.decode(untagged), Ok(value.clone()));
+
+            let tagged: Value = Encoding::Tagged.encode(&value);
+            assert_eq!(Encoding::Tagged.decode(tagged), Ok(value));
+        }
+
+        assert_eq!(Encoding::Tagged.encode(&Example1::AnInteger(5)), json!({"AnInteger": 5}));
+        // a plain string is untagged
+        assert!(matches!(Encoding::Tagged.decode(json!("on")), Err(StoreError::InvalidValue { encoding: Encoding::Tagged, .. })));
+
+        // numbers that don't fit an i32 are floats
+        assert_eq!(Encoding::Untagged.decode(json!(3_000_000_000u32)), Ok(Example1::AFloat(3_000_000_000.0)));
+        assert_eq!("UNTAGGED".parse::<Encoding>(), Ok(Encoding::Untagged));
+    }
+
+    fn contract_with_values() -> Contract {
+        env_setup();
+        let mut contract = Contract::default();
+
+        contract.put(String::from("ui.theme"), json!("dark"), Some(String::from("untagged")));
+        contract.put(String::from("ui.font_size"), json!({"AnInteger": 14}), None);
+        contract.put(String::from("limits.ratio"), json!(0.5), Some(String::from("untagged")));
+        contract.put(String::from("limits.range"), json!([-10, 10]), Some(String::from("untagged")));
+        contract.put(String::from("ui.zoom"), json!(null), Some(String::from("untagged")));
+
+        contract
+    }
+
+    #[test]
+    fn put_and_get() {
+        let mut contract = contract_with_values();
+        assert_logged!(value_put, ["ui.zoom", "NoValue"]);
+
+        assert_eq!(contract.get(String::from("ui.theme"), None), json!({"AString": "dark"}));
+        assert_eq!(contract.get(String::from("ui.font_size"), Some(String::from("untagged"))), json!(14));
+
+        assert_eq!(contract.get_i32(String::from("ui.font_size")), 14);
+        assert_eq!(contract.get_f32(String::from("limits.ratio")), 0.5);
+        assert_eq!(contract.get_string(String::from("ui.theme")), "dark");
+        assert_eq!(contract.get_tuple(String::from("limits.range")), (-10, 10));
+
+        // replace, then remove
+        contract.put(String::from("ui.theme"), json!("light"), Some(String::from("untagged")));
+        assert_eq!(contract.get_string(String::from("ui.theme")), "light");
+        assert!(contract.remove(String::from("ui.theme")));
+        assert!(!contract.remove(String::from("ui.theme")));
+        assert_logged!(value_removed, ["ui.theme"]);
+
+        let actions: Vec<Action> = contract.list_actions(None, None, None, None, Some(5), None).records.into_iter().map(|record| record.action).collect();
+        assert_eq!(actions, vec![
+            Action::ValuePut { key: String::from("ui.theme"), kind: Example1Kind::AString },
+            Action::ValueRemoved { key: String::from("ui.theme") },
+        ]);
+
+        contract.put(String::from("limits.owner"), json!({"first": 7, "second": "admin"}), Some(String::from("untagged")));
+        assert_eq!(contract.get_struct(String::from("limits.owner")), (7, String::from("admin")));
+        assert!(contract.remove(String::from("limits.owner")));
+    }
+
+    #[test]
+    fn errors() {
+        let mut contract = contract_with_values();
+
+        assert_panics!(type_mismatch, contract.get_string(String::from("ui.font_size")), ["ui.font_size", "AnInteger", "AString"]);
+        assert_panics!(type_mismatch, contract.get_i32(String::from("ui.zoom")), ["NoValue", "AnInteger"]);
+        assert_panics!(type_mismatch, contract.get_struct(String::from("limits.range")), ["limits.range", "ATuple", "ACLikeStruct"]);
+        assert_panics!(value_not_found, contract.get_i32(String::from("ui.color")), ["ui.color"]);
+        assert_panics!(value_not_found, contract.get(String::from("ui.color"), None));
+        assert_panics!(invalid_encoding, contract.get(String::from("ui.theme"), Some(String::from("yaml"))), ["yaml"]);
+        assert_panics!(invalid_value, contract.put(String::from("ui.theme"), json!("dark"), None), ["Tagged"]);
+        assert_panics!(invalid_value, contract.put(String::from("ui.theme"), json!([1]), Some(String::from("untagged"))));
+
+        env_setup_caller("alice.near");
+        let message = contract_log::testing::panic_message(|| contract.put(String::from("ui.theme"), json!(null), None)).unwrap();
+        assert!(message.contains("Only owner's account is allowed"), "{}", message);
+    }
+
+    #[test]
+    fn kinds() {
+        let contract = contract_with_values();
+
+        assert_eq!(contract.get_kind(String::from("ui.font_size")), Example1Kind::AnInteger);
+        assert_eq!(contract.get_kind(String::from("limits.ratio")), Example1Kind::AFloat);
+        assert!(contract.is_no_value(String::from("ui.zoom")));
+        assert!(!contract.is_no_value(String::from("ui.theme")));
+
+        // the kind is the one a type_mismatch reports
+        assert_eq!(contract.get_kind(String::from("ui.theme")), Example1Kind::AString);
+        assert_panics!(type_mismatch, contract.get_tuple(String::from("ui.theme")), ["ui.theme", "AString", "ATuple"]);
+        assert_eq!(contract.get_kind(String::from("ui.zoom")), Example1Kind::NoValue);
+        assert_panics!(type_mismatch, contract.get_f32(String::from("ui.zoom")), ["ui.zoom", "NoValue", "AFloat"]);
+
+        assert_panics!(value_not_found, contract.get_kind(String::from("ui.color")), ["ui.color"]);
+        assert_panics!(value_not_found, contract.is_no_value(String::from("ui.color")), ["ui.color"]);
+    }
+
+    #[test]
+    fn list_keys() {
+        let contract = contract_with_values();
+
+        assert_eq!(
+            contract.list_keys(None, None, None),
+            vec!["limits.range", "limits.ratio", "ui.font_size", "ui.theme", "ui.zoom"],
+        );
+        assert_eq!(contract.list_keys(Some(String::from("ui.")), None, None), vec!["ui.font_size", "ui.theme", "ui.zoom"]);
+        assert_eq!(contract.list_keys(Some(String::from("limits.")), None, Some(1)), vec!["limits.range"]);
+
+        // pages
+        assert_eq!(contract.list_keys(Some(String::from("ui.")), Some(String::from("ui.font_size")), Some(1)), vec!["ui.theme"]);
+        assert_eq!(contract.list_keys(Some(String::from("ui.")), Some(String::from("ui.zoom")), None), Vec::<String>::new());
+        // from before the prefix starts at the prefix
+        assert_eq!(contract.list_keys(Some(String::from("ui.")), Some(String::from("a")), Some(1)), vec!["ui.font_size"]);
+        assert_eq!(contract.list_keys(Some(String::from("network.")), None, None), Vec::<String>::new());
+    }
+}
@@ -0,0 +1,832 @@
+//! Contract functions for the registry of users (Example2User).
+//!
+//! Users are stored by id in a TreeMap, so list_users returns them in order of id.
+//! Each user is linked to an account, so require_permission knows who is calling.
+//!
+//!  - The owner creates Admins, and is the only one allowed to change or delete them.
+//!  - The owner, and users with Permission::ManageUsers, create and manage Employees and Clients.
+//!    A user can't give an Employee a permission it doesn't have itself, nor move the account of a user
+//!    with such a permission; otherwise ManageUsers would be enough to get any permission.
+//!  - Only the owner changes permissions.
+//!
+//! Every change is recorded in the audit log, see the module audit.
+//!
+
+use std::ops::Bound;
+
+use near_sdk::{env, near_bindgen, AccountId};
+
+use contract_log::{contract_log, contract_panic};
+
+use crate::{
+    audit::{Action, ActionPage},
+    log_call,
+    migration::PlaintextContract,
+    model::{Employee, Example2User, Role},
+    password::Password,
+    permission::{Permission, Permissions},
+    Contract,
+};
+
+
+/// Most users list_users returns at once.
+pub const MAX_LIST_LIMIT: u32 = 100;
+
+/// Users list_users returns when no limit is given.
+pub const DEFAULT_LIST_LIMIT: u32 = 10;
+
+
+#[near_bindgen]
+impl Contract{
+    /// Creates an Admin with every permission. Returns the new user id.
+    ///
+    /// Only owner can call this function.
+    ///
+    /// # Panics
+    ///  - If caller is not owner.
+    ///  - If account_id already has a user.
+    pub fn create_admin(&mut self, account_id: AccountId, name: String, password: String) -> u32 {
+        log_call("create_admin");
+        self.assert_owner_only();
+
+        let id: u32 = self.next_user_id;
+        self.add_user(Some(account_id), Example2User::Admin {
+            name,
+            id,
+            pass: Password::new(&password),
+            permissions: Permissions::all(),
+        })
+    }
+
+    /// Creates an Employee. Returns the new user id.
+    ///
+    /// permissions are names of Permission, e.g. ["stock", "ManageOrders"].
+    ///
+    /// # Panics
+    ///  - If caller doesn't have Permission::ManageUsers.
+    ///  - If caller doesn't have every permission it gives. The owner has them all.
+    ///  - If account_id already has a user.
+    ///  - If a permission isn't a valid name.
+    pub fn create_employee(&mut self, account_id: AccountId, name: String, password: String, permissions: Vec<String>) -> u32 {
+        log_call("create_employee");
+        self.require_permission(Permission::ManageUsers);
+
+        let permissions: Permissions = permissions.iter().map(|name| parse_permission(name)).collect();
+        let caller_permissions: Permissions = self.caller_permissions();
+
+        if let Some(missing) = permissions.list().into_iter().find(|permission| !caller_permissions.contains(*permission)) {
+            contract_panic!([permission_not_held] "{} can't give permission {}, it doesn't have it.", env::predecessor_account_id(), missing);
+        }
+
+        let id: u32 = self.next_user_id;
+        self.add_user(Some(account_id), Example2User::Employee(Employee {
+            name,
+            id,
+            pass: Password::new(&password),
+            permissions,
+        }))
+    }
+
+    /// Creates a Client. Returns the new user id.
+    ///
+    /// # Panics
+    ///  - If caller doesn't have Permission::ManageUsers.
+    ///  - If account_id already has a user.
+    pub fn create_client(&mut self, account_id: AccountId, name: String) -> u32 {
+        log_call("create_client");
+        self.require_permission(Permission::ManageUsers);
+
+        let id: u32 = self.next_user_id;
+        self.add_user(Some(account_id), Example2User::Client {
+            name,
+            id,
+            orders: vec![],
+        })
+    }
+
+    /// Changes the name or the account of a user. Arguments left out are not changed.
+    ///
+    /// # Panics
+    ///  - If the user doesn't exist.
+    ///  - If the user is an Admin and caller is not owner.
+    ///  - If the user isn't an Admin and caller doesn't have Permission::ManageUsers.
+    ///  - If account_id is given, the user has a permission the caller doesn't, and caller is not owner.
+    ///  - If account_id already has a user.
+    pub fn update_user(&mut self, user_id: u32, name: Option<String>, account_id: Option<AccountId>) {
+        log_call("update_user");
+
+        let mut user: Example2User = self.stored_user(user_id);
+        self.assert_can_manage(&user);
+
+        if let Some(name) = name {
+            user.set_name(name);
+            self.users.insert(&user_id, &user);
+        }
+
+        if let Some(account_id) = account_id {
+            // whoever controls account_id gets the permissions of the user
+            let permissions: Permissions = user.permissions().copied().unwrap_or_default();
+
+            if !permissions.is_subset(&self.caller_permissions()) {
+                contract_panic!([account_change_owner_only] "Only the owner can change the account of user {}, it has permissions {} doesn't have.", user_id, env::predecessor_account_id());
+            }
+
+            self.unlink_account(user_id);
+            self.link_account(account_id, user_id);
+        }
+
+        self.record(Action::UserUpdated { user_id });
+        contract_log!(info, [user_updated] "Updated user {}.", user_id);
+    }
+
+    /// Deletes a user.
+    ///
+    /// # Panics
+    ///  - If the user doesn't exist.
+    ///  - If the user is an Admin and caller is not owner.
+    ///  - If the user isn't an Admin and caller doesn't have Permission::ManageUsers.
+    pub fn delete_user(&mut self, user_id: u32) {
+        log_call("delete_user");
+
+        let user: Example2User = self.stored_user(user_id);
+        self.assert_can_manage(&user);
+
+        self.unlink_account(user_id);
+        self.users.remove(&user_id);
+
+        self.record(Action::UserDeleted { user_id });
+        contract_log!(info, [user_deleted] "Deleted user {}.", user_id);
+    }
+
+    /// Returns a user. The password is never included.
+    pub fn get_user(&self, user_id: u32) -> Example2User {
+        log_call("get_user");
+        self.stored_user(user_id)
+    }
+
+    /// Lists users in order of id.
+    ///
+    ///  - from: first id to include. Default 0.
+    ///  - limit: most users returned. Default DEFAULT_LIST_LIMIT, at most MAX_LIST_LIMIT.
+    ///  - role_filter: only users with this role, "admin", "client" or "employee".
+    ///
+    /// To get the next page, call again with from set to the last id returned plus one.
+    pub fn list_users(&self, from: Option<u32>, limit: Option<u32>, role_filter: Option<String>) -> Vec<Example2User> {
+        log_call("list_users");
+
+        let limit: u32 = limit.unwrap_or(DEFAULT_LIST_LIMIT).min(MAX_LIST_LIMIT);
+        let role: Option<Role> = role_filter.map(|role| match role.parse() {
+            Ok(role) => role,
+            Err(err) => panic!("{}", err),
+        });
+
+        self.users
+            .range((Bound::Included(from.unwrap_or(0)), Bound::Unbounded))
+            .map(|(_, user)| user)
+            .filter(|user| match role {
+                Some(role) => user.role() == role,
+                None => true,
+            })
+            .take(limit as usize)
+            .collect()
+    }
+
+    /// Calls Example2User::get_name.
+    pub fn get_name(&self, user_id: u32) -> String {
+        log_call("get_name");
+        self.stored_user(user_id).get_name()
+    }
+
+    /// Calls Example2User::has_permission.
+    ///
+    /// permission is the name of a Permission, e.g. "AccessStock" or "stock".
+    pub fn has_permission(&self, user_id: u32, permission: String) -> bool {
+        log_call("has_permission");
+        self.stored_user(user_id).has_permission(parse_permission(&permission))
+    }
+
+    /// Actions made by an Admin or Employee, and actions about them, in order of time.
+    ///
+    /// Returns up to limit actions (default DEFAULT_LIST_LIMIT, at most MAX_LIST_LIMIT), reading from the id from_index.
+    /// Reads at most audit::MAX_SCAN records. To get the next page, call again with from_index set to next_index.
+    /// To filter them, use list_actions.
+    ///
+    /// When returing a struct, the default serializer is serde (serde::json).
+    /// So let's be explicit about using borsh.
+    ///
+    /// # Panics
+    ///  - If the user doesn't exist, or is a Client.
+    #[result_serializer(borsh)]
+    pub fn get_actions(&self, user_id: u32, from_index: Option<u64>, limit: Option<u32>) -> ActionPage{
+        log_call("get_actions");
+
+        let result = self.user_actions(user_id, from_index, limit);
+
+        let result: ActionPage = match result {
+            Err(err) => panic!("Error: {}\n", err),
+            Ok(value) => value,
+        };
+
+        result
+    }
+
+    /// Gives a permission to a user.
+    ///
+    /// Only owner can call this function.
+    ///
+    /// # Panics
+    ///  - If caller is not owner.
+    ///  - If permission isn't a valid name.
+    ///  - If the user doesn't exist, is a Client, or already has the permission.
+    pub fn grant_permission(&mut self, user_id: u32, permission: String) {
+        log_call("grant_permission");
+        self.assert_owner_only();
+
+        let permission: Permission = parse_permission(&permission);
+        let mut user: Example2User = self.stored_user(user_id);
+
+        if let Err(err) = user.grant_permission(permission) {
+            panic!("Error: {}\n", err);
+        }
+
+        self.users.insert(&user_id, &user);
+
+        self.record(Action::PermissionGranted { user_id, permission });
+        contract_log!(info, [permission_granted] "Granted {} to user {}.", permission, user_id);
+    }
+
+    /// Takes a permission from a user.
+    ///
+    /// Only owner can call this function.
+    ///
+    /// # Panics
+    ///  - If caller is not owner.
+    ///  - If permission isn't a valid name.
+    ///  - If the user doesn't exist, is a Client, or doesn't have the permission.
+    pub fn revoke_permission(&mut self, user_id: u32, permission: String) {
+        log_call("revoke_permission");
+        self.assert_owner_only();
+
+        let permission: Permission = parse_permission(&permission);
+        let mut user: Example2User = self.stored_user(user_id);
+
+        if let Err(err) = user.revoke_permission(permission) {
+            panic!("Error: {}\n", err);
+        }
+
+        self.users.insert(&user_id, &user);
+
+        self.record(Action::PermissionRevoked { user_id, permission });
+        contract_log!(info, [permission_revoked] "Revoked {} from user {}.", permission, user_id);
+    }
+
+    /// Permissions of a user. Empty for Clients.
+    ///
+    /// Only owner can call this function.
+    ///
+    /// # Panics
+    ///  - If caller is not owner.
+    ///  - If the user doesn't exist.
+    pub fn list_permissions(&self, user_id: u32) -> Vec<Permission> {
+        log_call("list_permissions");
+        self.assert_owner_only();
+
+        self.stored_user(user_id).permissions().map(|permissions| permissions.list()).unwrap_or_default()
+    }
+
+    /// Returns true if candidate is the password of the user with this id.
+    ///
+    /// Clients don't have passwords, always false.
+    ///
+    /// # Panics
+    ///  - If there's no user with this id.
+    pub fn verify_password(&self, user_id: u32, candidate: String) -> bool {
+        log_call("verify_password");
+
+        self.stored_user(user_id).verify_password(&candidate)
+    }
+
+    /// Replaces the password of the user with this id.
+    ///
+    /// # Panics
+    ///  - If there's no user with this id.
+    ///  - If the user is a Client, or current is not the password.
+    pub fn change_password(&mut self, user_id: u32, current: String, new: String) {
+        log_call("change_password");
+
+        let mut user: Example2User = self.stored_user(user_id);
+
+        if let Err(err) = user.change_password(&current, &new) {
+            panic!("Error: {}\n", err);
+        }
+
+        self.users.insert(&user_id, &user);
+
+        self.record(Action::PasswordChanged { user_id });
+        contract_log!(info, [password_changed] "Changed password of user {}.", user_id);
+    }
+
+    /// Reads the state of the first version, from before passwords were hashed, and hashes the password.
+    ///
+    /// The old example_2_user is moved to the registry, keeping its id. It has no account.
+    /// Its free text actions, and its free text permissions that aren't a Permission, are added to the audit log
+    /// as Action::Imported, made by the contract account.
+    ///
+    /// Deploy the new code and call this function once, in the same transaction.
+    ///
+    /// Only the contract account can call this function.
+    #[private]
+    #[init(ignore_state)]
+    pub fn migrate_plaintext_passwords() -> Self {
+        log_call("migrate_plaintext_passwords");
+
+        let old: PlaintextContract = match env::state_read() {
+            Some(old) => old,
+            None => panic!("No state to migrate."),
+        };
+
+        let mut contract = Contract {
+            example_0: old.example_0,
+            example_1: old.example_1,
+            ..Contract::default()
+        };
+
+        let (user, descriptions) = old.example_2_user.into_user();
+        let user_id: u32 = contract.add_user(None, user);
+
+        for description in descriptions {
+            contract.record(Action::Imported { user_id, description });
+        }
+
+        contract
+    }
+}
+
+
+impl Contract {
+    // Stores a new user, and links it to an account. Returns the user id.
+    fn add_user(&mut self, account_id: Option<AccountId>, user: Example2User) -> u32 {
+        let user_id: u32 = user.get_id();
+
+        assert!(!self.users.contains_key(&user_id), "User {} already exists.", user_id);
+
+        if let Some(account_id) = account_id {
+            self.link_account(account_id, user_id);
+        }
+
+        self.users.insert(&user_id, &user);
+        self.next_user_id = self.next_user_id.max(user_id + 1);
+
+        self.record(Action::UserCreated { user_id, role: user.role() });
+        contract_log!(info, [user_created] "Created {} {} with id {}.", user.role(), user.get_name(), user_id);
+
+        user_id
+    }
+
+    fn link_account(&mut self, account_id: AccountId, user_id: u32) {
+        if let Some(other) = self.user_ids.get(&account_id) {
+            panic!("Account {} already has user {}.", account_id, other);
+        }
+
+        self.user_ids.insert(&account_id, &user_id);
+        self.user_accounts.insert(&user_id, &account_id);
+    }
+
+    fn unlink_account(&mut self, user_id: u32) {
+        if let Some(account_id) = self.user_accounts.remove(&user_id) {
+            self.user_ids.remove(&account_id);
+        }
+    }
+
+    // Only owner manages Admins, ManageUsers is enough for everyone else
+    fn assert_can_manage(&self, user: &Example2User) {
+        match user.role() {
+            Role::Admin => self.assert_owner_only(),
+            _ => self.require_permission(Permission::ManageUsers),
+        }
+    }
+
+    pub(crate) fn stored_user(&self, user_id: u32) -> Example2User {
+        match self.users.get(&user_id) {
+            Some(user) => user,
+            None => panic!("User {} not found.", user_id),
+        }
+    }
+}
+
+
+// Permissions are received as Strings, invalid names panic with the list of valid ones
+fn parse_permission(name: &str) -> Permission {
+    match name.parse() {
+        Ok(permission) => permission,
+        Err(err) => panic!("{}", err),
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{env_setup, env_setup_caller};
+    use crate::model::{Example0, Example1};
+    use crate::order::{Order, OrderStatus};
+    use contract_log::{assert_logged, assert_panics, testing::panic_message};
+
+    // Contract with an employee (alice.near, id 0) and a client (bob.near, id 1)
+    fn contract_with_users() -> Contract {
+        env_setup();
+        let mut contract = Contract::default();
+
+        contract.create_employee(
+            String::from("alice.near"),
+            String::from("Alice"),
+            String::from("alice's pass"),
+            vec![String::from("client_logs"), String::from("stock")],
+        );
+        contract.create_client(String::from("bob.near"), String::from("Bob"));
+
+        contract
+    }
+
+    fn ids(users: &[Example2User]) -> Vec<u32> {
+        users.iter().map(|user| user.get_id()).collect()
+    }
+
+    #[test]
+    fn create_users(){
+        let mut contract = contract_with_users();
+
+        let admin: u32 = contract.create_admin(String::from("carol.near"), String::from("Carol"), String::from("pass"));
+
+        assert_eq!(admin, 2);
+        assert_eq!(contract.get_name(0), "Alice");
+        assert_eq!(contract.get_user(1).role(), Role::Client);
+        assert_eq!(contract.list_permissions(admin), Permission::ALL.to_vec());
+        assert!(contract.has_permission(0, String::from("stock")));
+        assert!(!contract.has_permission(1, String::from("stock")));
+        assert_eq!(contract.get_actions(0, None, None).records.len(), 1);
+        assert_logged!(user_created, ["Carol", 2]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Account alice.near already has user 0.")]
+    fn create_user_same_account(){
+        let mut contract = contract_with_users();
+
+        contract.create_client(String::from("alice.near"), String::from("Alice again"));
+    }
+
+    #[test]
+    fn create_users_with_permission(){
+        let mut contract = contract_with_users();
+
+        // alice can't create users yet
+        env_setup_caller("alice.near");
+        assert_panics!(missing_permission, contract.create_client(String::from("dave.near"), String::from("Dave")));
+
+        env_setup();
+        contract.grant_permission(0, String::from("users"));
+
+        env_setup_caller("alice.near");
+        let dave: u32 = contract.create_client(String::from("dave.near"), String::from("Dave"));
+        assert_eq!(contract.get_name(dave), "Dave");
+
+        // but only the owner creates admins
+        let message = panic_message(|| {
+            contract.create_admin(String::from("eve.near"), String::from("Eve"), String::from("pass"));
+        });
+        assert!(message.unwrap_or_default().contains("Only owner's account is allowed"));
+    }
+
+    #[test]
+    fn update_and_delete_user(){
+        let mut contract = contract_with_users();
+
+        contract.update_user(1, Some(String::from("Robert")), Some(String::from("robert.near")));
+        assert_eq!(contract.get_name(1), "Robert");
+        assert_logged!(user_updated, [1]);
+
+        // the old account is free again
+        let other: u32 = contract.create_client(String::from("bob.near"), String::from("Another Bob"));
+
+        contract.delete_user(1);
+        assert_logged!(user_deleted, [1]);
+        assert_eq!(ids(&contract.list_users(None, None, None)), vec![0, other]);
+
+        // and so is the account of the deleted user
+        contract.create_client(String::from("robert.near"), String::from("Robert"));
+    }
+
+    #[test]
+    fn create_employee_without_escalation(){
+        let mut contract = contract_with_users();
+
+        // alice can manage users, but not orders
+        contract.revoke_permission(0, String::from("client_logs"));
+        contract.grant_permission(0, String::from("users"));
+
+        env_setup_caller("alice.near");
+        assert_panics!(
+            permission_not_held,
+            contract.create_employee(String::from("dave.near"), String::from("Dave"), String::from("pass"), vec![String::from("orders")]),
+            ["alice.near", "ManageOrders"]
+        );
+
+        // not even to an account alice controls
+        assert_panics!(
+            permission_not_held,
+            contract.create_employee(String::from("alice2.near"), String::from("Alice"), String::from("pass"), vec![String::from("stock"), String::from("orders")])
+        );
+
+        // permissions alice has can be given
+        let dave: u32 = contract.create_employee(String::from("dave.near"), String::from("Dave"), String::from("pass"), vec![String::from("stock")]);
+
+        env_setup();
+        assert_eq!(contract.list_permissions(dave), vec![Permission::AccessStock]);
+    }
+
+    #[test]
+    fn update_user_account_without_escalation(){
+        let mut contract = contract_with_users();
+
+        let dave: u32 = contract.create_employee(String::from("dave.near"), String::from("Dave"), String::from("pass"), vec![String::from("orders")]);
+        contract.grant_permission(0, String::from("users"));
+
+        // alice can't take over dave's account, dave has ManageOrders and alice doesn't
+        env_setup_caller("alice.near");
+        assert_panics!(
+            account_change_owner_only,
+            contract.update_user(dave, None, Some(String::from("alice2.near"))),
+            [dave, "alice.near"]
+        );
+        assert_panics!(missing_permission, contract.require_permission(Permission::ManageOrders));
+
+        // but can rename dave, and move users without a permission alice lacks
+        contract.update_user(dave, Some(String::from("David")), None);
+        contract.update_user(1, None, Some(String::from("robert.near")));
+
+        // the owner can move anyone
+        env_setup();
+        contract.update_user(dave, None, Some(String::from("david.near")));
+    }
+
+    #[test]
+    #[should_panic(expected = "Only owner's account is allowed to make this function call.")]
+    fn delete_admin_owner_only(){
+        let mut contract = contract_with_users();
+        let admin: u32 = contract.create_admin(String::from("carol.near"), String::from("Carol"), String::from("pass"));
+
+        contract.grant_permission(0, String::from("users"));
+
+        env_setup_caller("alice.near");
+        contract.delete_user(admin);
+    }
+
+    #[test]
+    #[should_panic(expected = "User 7 not found.")]
+    fn get_missing_user(){
+        let contract = contract_with_users();
+
+        contract.get_user(7);
+    }
+
+    #[test]
+    fn list_users(){
+        env_setup();
+        let mut contract = Contract::default();
+
+        for index in 0..25 {
+            let account_id: AccountId = format!("user{}.near", index);
+
+            match index % 5 {
+                0 => contract.create_employee(account_id, format!("Employee {}", index), String::from("pass"), vec![]),
+                _ => contract.create_client(account_id, format!("Client {}", index)),
+            };
+        }
+
+        // default page
+        assert_eq!(ids(&contract.list_users(None, None, None)), (0..10).collect::<Vec<u32>>());
+
+        // next pages
+        assert_eq!(ids(&contract.list_users(Some(10), Some(10), None)), (10..20).collect::<Vec<u32>>());
+        assert_eq!(ids(&contract.list_users(Some(20), Some(10), None)), (20..25).collect::<Vec<u32>>());
+        assert!(contract.list_users(Some(25), None, None).is_empty());
+
+        // filtered by role
+        assert_eq!(ids(&contract.list_users(None, Some(3), Some(String::from("employee")))), vec![0, 5, 10]);
+        assert_eq!(ids(&contract.list_users(Some(11), None, Some(String::from("Employee")))), vec![15, 20]);
+        assert!(contract.list_users(None, None, Some(String::from("admin"))).is_empty());
+
+        // limit is capped
+        assert_eq!(contract.list_users(None, Some(1000), None).len(), 25);
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid name for Role")]
+    fn list_users_invalid_role(){
+        let contract = contract_with_users();
+
+        contract.list_users(None, None, Some(String::from("manager")));
+    }
+
+    #[test]
+    fn grant_and_revoke_permission(){
+        let mut contract = contract_with_users();
+
+        assert_eq!(
+            contract.list_permissions(0),
+            vec![Permission::AccessClientLogs, Permission::AccessStock],
+        );
+
+        contract.grant_permission(0, String::from("orders"));
+        contract.revoke_permission(0, String::from("AccessStock"));
+
+        assert_eq!(
+            contract.list_permissions(0),
+            vec![Permission::AccessClientLogs, Permission::ManageOrders],
+        );
+        assert_logged!(permission_granted, ["ManageOrders", 0]);
+        assert_logged!(permission_revoked, ["AccessStock", 0]);
+
+        assert!(contract.list_permissions(1).is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "Only owner's account is allowed to make this function call.")]
+    fn grant_permission_owner_only(){
+        let mut contract = contract_with_users();
+
+        env_setup_caller("alice.near");
+        contract.grant_permission(0, String::from("users"));
+    }
+
+    #[test]
+    #[should_panic(expected = "User is Client")]
+    fn grant_permission_to_client(){
+        let mut contract = contract_with_users();
+
+        contract.grant_permission(1, String::from("stock"));
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid name for Permission")]
+    fn grant_free_text_permission(){
+        let mut contract = contract_with_users();
+
+        contract.grant_permission(0, String::from("Can access 9gag"));
+    }
+
+    #[test]
+    fn require_permission(){
+        let mut contract = contract_with_users();
+
+        // the owner has every permission
+        contract.require_permission(Permission::ManageUsers);
+
+        env_setup_caller("alice.near");
+        contract.require_permission(Permission::AccessStock);
+        assert_panics!(missing_permission, contract.require_permission(Permission::ManageUsers), ["alice.near", "ManageUsers"]);
+
+        env_setup_caller("bob.near");
+        assert_panics!(missing_permission, contract.require_permission(Permission::AccessStock));
+
+        env_setup_caller("carol.near");
+        assert_panics!(missing_permission, contract.require_permission(Permission::AccessStock));
+
+        // revoking takes effect on the next call
+        env_setup();
+        contract.revoke_permission(0, String::from("stock"));
+        env_setup_caller("alice.near");
+        assert_panics!(missing_permission, contract.require_permission(Permission::AccessStock));
+    }
+
+    #[test]
+    fn passwords(){
+        let mut contract = contract_with_users();
+
+        assert!(contract.verify_password(0, String::from("alice's pass")));
+        assert!(!contract.verify_password(0, String::from("some other pass")));
+
+        contract.change_password(0, String::from("alice's pass"), String::from("new pass"));
+        assert!(contract.verify_password(0, String::from("new pass")));
+        assert_logged!(password_changed, [0]);
+
+        // bob is a Client
+        assert!(!contract.verify_password(1, String::from("")));
+
+        let json: String = near_sdk::serde_json::to_string(&contract.list_users(None, None, None)).unwrap();
+        assert!(!json.contains("pass"), "{}", json);
+    }
+
+    #[test]
+    #[should_panic(expected = "Wrong password")]
+    fn change_password_wrong(){
+        let mut contract = contract_with_users();
+
+        contract.change_password(0, String::from("wrong"), String::from("new pass"));
+    }
+
+    // Borsh bytes of a String: its length as u32, then its UTF-8 bytes
+    fn borsh_string(bytes: &mut Vec<u8>, text: &str) {
+        bytes.extend((text.len() as u32).to_le_bytes());
+        bytes.extend(text.as_bytes());
+    }
+
+    fn borsh_strings(bytes: &mut Vec<u8>, texts: &[&str]) {
+        bytes.extend((texts.len() as u32).to_le_bytes());
+
+        for text in texts {
+            borsh_string(bytes, text);
+        }
+    }
+
+    // The state of the first version: example_0 (Third), example_1 (NoValue), then example_2_user.
+    // user is the bytes of example_2_user, starting with its variant index.
+    fn write_first_version(user: Vec<u8>) {
+        let mut bytes: Vec<u8> = vec![2, 0];
+        bytes.extend(user);
+
+        env::storage_write(b"STATE", &bytes);
+    }
+
+    #[test]
+    fn migrate_plaintext_passwords(){
+        env_setup();
+
+        // Employee { name, id, pass, permissions: Vec<String>, actions: Vec<String> }
+        let mut employee: Vec<u8> = vec![2];
+        borsh_string(&mut employee, "a name");
+        employee.extend(11u32.to_le_bytes());
+        borsh_string(&mut employee, "some random pass");
+        borsh_strings(&mut employee, &["Can access google", "Manage Orders", "stock"]);
+        borsh_strings(&mut employee, &["Did something", "Did something else"]);
+
+        write_first_version(employee);
+
+        let contract = Contract::migrate_plaintext_passwords();
+
+        assert_eq!(contract.get_example_0(), Example0::Third);
+        assert_eq!(contract.get_example_1(), Example1::NoValue);
+
+        assert!(contract.verify_password(11, String::from("some random pass")));
+        assert!(!contract.verify_password(11, String::from("wrong")));
+        assert_eq!(ids(&contract.list_users(None, None, None)), vec![11]);
+        assert_eq!(contract.list_permissions(11), vec![Permission::AccessStock, Permission::ManageOrders]);
+
+        // old actions, and permissions that couldn't be converted, are in the audit log, made by the owner
+        let imported: Vec<(AccountId, Action)> = contract
+            .list_actions(None, Some(String::from("imported")), None, None, None, None)
+            .records
+            .into_iter()
+            .map(|record| (record.actor, record.action))
+            .collect();
+
+        let owner = || String::from("stiltztinkerstein");
+        let imported_action = |description: &str| Action::Imported { user_id: 11, description: String::from(description) };
+
+        assert_eq!(imported, vec![
+            (owner(), imported_action("Did something")),
+            (owner(), imported_action("Did something else")),
+            (owner(), imported_action("Permission not converted: Can access google")),
+        ]);
+    }
+
+    #[test]
+    fn migrate_plaintext_passwords_admin(){
+        env_setup();
+
+        // Admin { name, id, pass, actions: Vec<String> }, without permissions
+        let mut admin: Vec<u8> = vec![0];
+        borsh_string(&mut admin, "Lucas");
+        admin.extend(3u32.to_le_bytes());
+        borsh_string(&mut admin, "admin pass");
+        borsh_strings(&mut admin, &[]);
+
+        write_first_version(admin);
+
+        let contract = Contract::migrate_plaintext_passwords();
+
+        assert!(contract.verify_password(3, String::from("admin pass")));
+
+        // Admin could do anything
+        assert_eq!(contract.list_permissions(3), Permission::ALL.to_vec());
+    }
+
+    #[test]
+    fn migrate_plaintext_passwords_client(){
+        env_setup();
+
+        // Client { name, id, orders: Vec<String> }
+        let mut client: Vec<u8> = vec![1];
+        borsh_string(&mut client, "Bob");
+        client.extend(2u32.to_le_bytes());
+        borsh_strings(&mut client, &["Successful transaction. ID: 4241235."]);
+
+        write_first_version(client);
+
+        let contract = Contract::migrate_plaintext_passwords();
+
+        // old orders were finished transactions
+        assert_eq!(contract.get_orders(2), vec![Order { id: 0, description: String::from("Successful transaction. ID: 4241235."), status: OrderStatus::Delivered }]);
+    }
+}
@@ -20,13 +20,16 @@ near_sdk::setup_alloc!();
 
 use crate::{
     temperature::temp_format::TemperatureUnit,
+    schedule::date::month::Month,
     utils::{
-        log,
+        parse_name,
         ViewGet,
     },
     entry::TemperatureReading,
 };
 
+use contract_log::contract_log;
+
 
 /// Used to access smart contract blockchain data
 /// 
@@ -130,21 +133,21 @@ impl Contract{
         self.assert_no_cross_contract();
         self.assert_owner_only();
 
-        log("Called add_user.");
+        contract_log!(debug, "Called add_user.");
 
         // test if account has a well formed format and follows some simple rules... this doesn't mean it ACTUALLY EXISTS in the blockchain!!
-        log("Validating Account ID.");
+        contract_log!(trace, "Validating Account ID.");
         let account_id = match ValidAccountId::try_from(account_id){
             Ok(value) => String::from(value),
             Err(err) => panic!("Invalid user account id: {}.", err),
         };
 
-        log("Checking if user already exists.");
+        contract_log!(trace, "Checking if user already exists.");
         let contains: bool = self.users.contains(&account_id);
         assert!(!contains, "User {} is already included in allowed list.", &account_id);
         
         // Create vector for user data
-        log("New user detected. Storing User.");
+        contract_log!(info, "New user detected. Storing User.");
         let user_vector: Vector<TemperatureReading> = Vector::new(StorageKey::UserEntry(String::from(&account_id)));
         let inserting = self.entries.insert(&account_id, &user_vector);
         
@@ -172,15 +175,15 @@ impl Contract{
         let owner_id: AccountId = env::current_account_id();
         assert_ne!(&owner_id[..], &account_id[..], "Owner account can't be removed from contract.");
 
-        log("Called remove_user");
+        contract_log!(debug, "Called remove_user");
 
-        log("Validating Account ID.");
+        contract_log!(trace, "Validating Account ID.");
         let account_id = match ValidAccountId::try_from(account_id){
             Ok(value) => String::from(value),
             Err(err) => panic!("Invalid user account id: {}.", err),
         };
 
-        log("Checking if user exists.");
+        contract_log!(trace, "Checking if user exists.");
         let contains: bool = self.users.contains(&account_id);
         // panic if user not in list
         assert!(contains, "User {} not found.", &account_id);
@@ -195,10 +198,10 @@ impl Contract{
 
         match self.users.remove(&account_id){
             true => {
-                log("User successfully removed.");
+                contract_log!(info, "User successfully removed.");
             },
             false => {
-                log("Unexpected Behavior. Account exists in entries but doesn't exist in user list.");
+                contract_log!(error, "Unexpected Behavior. Account exists in entries but doesn't exist in user list.");
             },
         };
     }
@@ -212,18 +215,17 @@ impl Contract{
     ///  - If cross-contract call.
     ///  - If user not in allowed user list.
     ///  - If caller is not owner
+    ///  - If unit_name is not a valid temperature unit.
     /// 
     pub fn set_default_temperature_unit(&mut self, unit_name: String) {
         self.assert_no_cross_contract();
         self.assert_owner_only();
 
-        log("Called set_default_temperature_unit");
+        contract_log!(debug, "Called set_default_temperature_unit");
 
-        let temperature_unit = TemperatureUnit::new(&unit_name);
+        let temperature_unit: TemperatureUnit = parse_name(&unit_name);
 
-        log(
-            &format!("Setting default temperature unit to {}", &temperature_unit)
-        );
+        contract_log!(info, "Setting default temperature unit to {}", &temperature_unit);
 
         self.temp_format = temperature_unit;
     }
@@ -261,22 +263,26 @@ impl Contract{
         self.assert_user_allowed();
         let user: AccountId = env::predecessor_account_id();
 
-        log("Called new_entry.");
+        contract_log!(debug, "Called new_entry.");
+
+        // Names are checked before anything else, so an invalid month or unit doesn't create an entry
+        let date: Option<(i32, Month, u8)> = date.map(|(year, month, day)| (year, parse_name(&month), day));
+        let temp_format: Option<TemperatureUnit> = temp_format.map(|unit_name| parse_name(&unit_name));
 
-        log("Creating Entry.");
+        contract_log!(trace, "Creating Entry.");
         let entry: TemperatureReading = TemperatureReading::new(time, date, &self.temp_format, temp_value, temp_format);
 
-        log("Acquiring entries for this user.");
+        contract_log!(trace, "Acquiring entries for this user.");
         let mut entries = match self.entries.get(&user){
             None => panic!("Unexpected Behavior: Failed to find entries for this user."),
             Some(value) => value,
         };
         
-        log("Pushing entry to Vector.");
+        contract_log!(trace, "Pushing entry to Vector.");
         entries.push(&entry);
         assert!(self.entries.insert(&user, &entries).is_some(), "Failed to replace vector");
 
-        log("Operation Successful.");
+        contract_log!(info, "Operation Successful.");
     }
 
 
@@ -365,7 +371,7 @@ impl Contract{
         let account_id: String = match account_id {
             None => env::predecessor_account_id(),
             Some(value) => {
-                log("Validating user account.");
+                contract_log!(trace, "Validating user account.");
 
                 match ValidAccountId::try_from(value){
                     Ok(account_id) => String::from(account_id),
@@ -392,7 +398,7 @@ impl Contract{
             &account_id,
         );
 
-        log(&format!("Successfully removed all entries for {}.", &account_id));
+        contract_log!(info, "Successfully removed all entries for {}.", &account_id);
     }
 
     // View Functions
@@ -14,7 +14,10 @@ use near_sdk::{
 
 
 use crate::{
-    schedule::Timestamp,
+    schedule::{
+        date::month::Month,
+        Timestamp,
+    },
     temperature::{
         Temperature,
         temp_format::TemperatureUnit,
@@ -42,14 +45,13 @@ impl TemperatureReading {
     ///  # Panics
     ///  - if temperature below absolute zero.
     ///  - On invalid day.
-    ///  - On invalid month.
     /// 
     pub fn new(
             time: Option<(u8, u8, f32)>,
-            date: Option<(i32, String, u8)>,
+            date: Option<(i32, Month, u8)>,
             temperature_unit: &TemperatureUnit, 
             temperature_value: f32, 
-            arg_temp: Option<String>,
+            arg_temp: Option<TemperatureUnit>,
         ) -> Self {
         
         TemperatureReading { 
@@ -34,13 +34,10 @@ impl Date{
     /// # Panics
     ///  - If day is negative.
     ///  - If day is higher than the max allowed for a particular month.
-    ///  - If month is an invalid String.
     /// 
-    pub fn new(day: u8, month: &str, year: i32) -> Date {
+    pub fn new(day: u8, month: Month, year: i32) -> Date {
         // Creates a year 
         let year: Year = Year::new(year);
-        // Creates a month
-        let month: Month = Month::new(month);
         // Creates a day
         let day: Day = Day::new(day, &month, &year);
 
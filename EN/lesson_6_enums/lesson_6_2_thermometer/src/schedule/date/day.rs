@@ -25,7 +25,7 @@
 //! # use lesson_6_2_thermometer::schedule::date::year::Year;
 //! 
 //! // not leap year
-//! let month = Month::new("feb");
+//! let month: Month = "feb".parse().unwrap();
 //! let year = Year::new(1971);
 //! 
 //! let day = Day::new(28, &month, &year);
@@ -34,7 +34,7 @@
 //! assert_eq!(String::from(&day), "28");
 //! 
 //! // leap year
-//! let month = Month::new("feb");
+//! let month: Month = "feb".parse().unwrap();
 //! let year = Year::new(1972);
 //! 
 //! let day = Day::new(29, &month, &year);
@@ -72,73 +72,65 @@ use near_sdk::{
     serde::{ Deserialize, Serialize },
 };
 
+use named_variants_derive::NamedVariants;
+
 
 /// Represents a month
 /// 
 /// Using serde(untagged) this enum will
 /// be represented as string (no tag)
 /// 
-#[derive(BorshDeserialize, BorshSerialize, Clone, Deserialize, Serialize)]
+/// NamedVariants converts names to a month, not case-sensitive.
+/// All possible values on the left are converted
+/// to an enum value on the right:
+/// 
+///  - "january", "jan", "janeiro", "enero", "ene" => Month::January("January")
+///  - "february", "feb", "fevereiro", "fev", "febrero" => Month::February("February")
+///  - "march", "mar", "março", "marzo" => Month::March("March")
+///  - "april", "apr", "abril", "abr" => Month::April("April")
+///  - "may", "maio", "mayo" => Month::May("May")
+///  - "june", "jun", "junho", "junio" => Month::June("June")
+///  - "july", "jul", "julho", "julio" => Month::July("July")
+///  - "august", "aug", "agosto", "ago" => Month::August("August")
+///  - "september", "sep", "setembro", "set", "septiembre" => Month::September("September")
+///  - "october", "octo", "oct", "outubro", "out", "octubre", "octu" => Month::October("October")
+///  - "november", "nov", "novembro", "noviembre" => Month::November("November")
+///  - "december", "dec", "dezembro", "dez", "diciembre", "dic" => Month::December("December")
+/// 
+/// An invalid name returns MonthNameError.
+/// 
+#[derive(BorshDeserialize, BorshSerialize, Clone, Deserialize, NamedVariants, Serialize)]
 #[serde(crate = "near_sdk::serde")]
 #[serde(untagged)]
 pub enum Month{
+    #[alias("jan", "janeiro", "enero", "ene")]
     January(String),
+    #[alias("feb", "fevereiro", "fev", "febrero")]
     February(String),
+    #[alias("mar", "março", "marzo")]
     March(String),
+    #[alias("apr", "abril", "abr")]
     April(String),
+    #[alias("maio", "mayo")]
     May(String),
+    #[alias("jun", "junho", "junio")]
     June(String),
+    #[alias("jul", "julho", "julio")]
     July(String),
+    #[alias("aug", "agosto", "ago")]
     August(String),
+    #[alias("sep", "setembro", "set", "septiembre")]
     September(String),
+    #[alias("octo", "oct", "outubro", "out", "octubre", "octu")]
     October(String),
+    #[alias("nov", "novembro", "noviembre")]
     November(String),
+    #[alias("dec", "dezembro", "dez", "diciembre", "dic")]
     December(String),
 }
 
 
 impl Month{
-    /// Create a month instance.
-    /// 
-    /// All possible values on the left are converted
-    /// to an enum value on the right:
-    /// 
-    ///  - "january", "jan", "janeiro", "enero", "ene" => Month::January("January")
-    ///  - "february", "feb", "fevereiro", "fev", "febrero" => Month::February("February")
-    ///  - "march", "mar", "março", "marzo" => Month::March("March")
-    ///  - "april", "apr", "abril", "abr" => Month::April("April")
-    ///  - "may", "maio", "mayo" => Month::May("May")
-    ///  - "june", "jun", "junho", "junio" => Month::June("June")
-    ///  - "july", "jul", "julho", "julio" => Month::July("July")
-    ///  - "august", "aug", "agosto", "ago" => Month::August("August")
-    ///  - "september", "sep", "setembro", "set", "septiembre" => Month::September("September")
-    ///  - "october", "octo", "oct", "outubro", "out", "octubre", "octu" => Month::October("October")
-    ///  - "november", "nov", "novembro", "noviembre" => Month::November("November")
-    ///  - "december", "dec", "dezembro", "dez", "diciembro", "dic" => Month::December("December")
-    /// 
-    /// # Panics
-    /// - if an invalid argument is provided. Month not valid.
-    /// 
-    pub fn new(month: &str) -> Self {
-        let lower_case: String = month.to_ascii_lowercase();
-        
-        match &lower_case[..]{
-            "january" | "jan" | "janeiro" | "enero" | "ene" => Month::January(String::from("January")),
-            "february" | "feb" | "fevereiro" | "fev" | "febrero" => Month::February(String::from("February")),
-            "march" | "mar" | "março" | "marzo" => Month::March(String::from("March")),
-            "april" | "apr" | "abril" | "abr" => Month::April(String::from("April")),
-            "may" | "maio" | "mayo" => Month::May(String::from("May")),
-            "june" | "jun" | "junho" | "junio" => Month::June(String::from("June")),
-            "july" | "jul" | "julho" | "julio" => Month::July(String::from("July")),
-            "august" | "aug" | "agosto" | "ago" => Month::August(String::from("August")),
-            "september" | "sep" | "setembro" | "set" | "septiembre" => Month::September(String::from("September")),
-            "october" | "octo" | "oct" | "outubro" | "out" | "octubre" | "octu" => Month::October(String::from("October")),
-            "november" | "nov" | "novembro" | "noviembre" => Month::November(String::from("November")),
-            "december" | "dec" | "dezembro" | "dez" | "diciembre" | "dic" => Month::December(String::from("December")),
-            invalid => panic!("Invalid value for month: {}.", invalid),
-        }
-    }
-
     // jan 31
     // feb 59
     // mar 90
@@ -157,7 +149,7 @@ impl Month{
     /// 
     /// if is_leap_year then the range can be 0 to 366.
     /// 
-    pub fn new_from_days(mut days: u64, is_leap_year: bool) -> (String, u8) {
+    pub fn new_from_days(mut days: u64, is_leap_year: bool) -> (Month, u8) {
         // 0 false 1 true
         let leap_year: u64 =  is_leap_year as u64;
         assert!(days < 365 + leap_year, "Unexpected Behavior. Days should be lower than {}. Got {}.", 365 + leap_year, days);
@@ -165,59 +157,46 @@ impl Month{
         days += 1;
 
         if days <= 31 {
-            return (String::from("jan"), days as u8);
+            return (Month::from(0), days as u8);
         }
         if days <= 59 + leap_year {
             // include feb 29 for leap year
-            return (String::from("feb"), days as u8 - 31);
+            return (Month::from(1), days as u8 - 31);
         }
         if days <= 90 + leap_year {
-            return (String::from("mar"), (days - 59 - leap_year) as u8);
+            return (Month::from(2), (days - 59 - leap_year) as u8);
         }
         if days <= 120 + leap_year {
-            return (String::from("apr"), (days - 90 - leap_year) as u8);
+            return (Month::from(3), (days - 90 - leap_year) as u8);
         }
         if days <= 151 + leap_year {
-            return (String::from("may"), (days - 120 - leap_year) as u8);
+            return (Month::from(4), (days - 120 - leap_year) as u8);
         }
         if days <= 181 + leap_year {
-            return (String::from("jun"), (days - 151 - leap_year) as u8);
+            return (Month::from(5), (days - 151 - leap_year) as u8);
         }
         if days <= 212 + leap_year {
-            return (String::from("jul"), (days - 181 - leap_year) as u8);
+            return (Month::from(6), (days - 181 - leap_year) as u8);
         }
         if days <= 243 + leap_year {
-            return (String::from("aug"), (days - 212 - leap_year) as u8);
+            return (Month::from(7), (days - 212 - leap_year) as u8);
         }
         if days <= 273 + leap_year {
-            return (String::from("sep"), (days - 243 - leap_year) as u8);
+            return (Month::from(8), (days - 243 - leap_year) as u8);
         }
         if days <= 304 + leap_year {
-            return (String::from("oct"), (days - 273 - leap_year) as u8);
+            return (Month::from(9), (days - 273 - leap_year) as u8);
         }
         if days <= 334 + leap_year {
-            return (String::from("nov"), (days - 304 - leap_year) as u8);
+            return (Month::from(10), (days - 304 - leap_year) as u8);
         }
 
-        (String::from("dec"), (days - 334 - leap_year) as u8)  
+        (Month::from(11), (days - 334 - leap_year) as u8)  
     }
 
     /// Returns month name as a String
     pub fn get(&self) -> String {
-        match self {
-            Month::January(value) => value.clone(),
-            Month::February(value) => value.clone(),
-            Month::March(value) => value.clone(),
-            Month::April(value) => value.clone(),
-            Month::May(value) => value.clone(),
-            Month::June(value) => value.clone(),
-            Month::July(value) => value.clone(),
-            Month::August(value) => value.clone(),
-            Month::September(value) => value.clone(),
-            Month::October(value) => value.clone(),
-            Month::November(value) => value.clone(),
-            Month::December(value) => value.clone(),
-        }
+        String::from(self.name())
     }
 }
 
@@ -242,23 +221,19 @@ impl From<&Month> for u8 {
 }
 
 /// Convert to Month from u8
+/// 
+/// # Panics
+///  - If month is not lower than 12.
+/// 
 impl From<u8> for Month {
     fn from(month: u8) -> Month {
-        match month{
-            0 => Month::new("jan"),
-            1 => Month::new("feb"),
-            2 => Month::new("mar"),
-            3 => Month::new("apr"),
-            4 => Month::new("may"),
-            5 => Month::new("jun"),
-            6 => Month::new("jul"),
-            7 => Month::new("aug"),
-            8 => Month::new("sep"),
-            9 => Month::new("oct"),
-            10 => Month::new("nov"),
-            11 => Month::new("dec"),
-            invalid => panic!("Invalid value for month: {}. Number value must be positive, lower than 12.", invalid),
-        }
+        let name: &str = match Month::ALL_VARIANTS.get(month as usize) {
+            Some(name) => name,
+            None => panic!("Invalid value for month: {}. Number value must be positive, lower than 12.", month),
+        };
+
+        // Every name in ALL_VARIANTS is valid
+        name.parse().unwrap()
     }
 }
 
@@ -277,20 +252,14 @@ impl From<Month> for String{
     }
 }
 
-/// String representation, useful for using format!, println! and panic!
-impl std::fmt::Display for Month {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", String::from(self))
-    }
-}
-
 
 
 
 #[cfg(test)]
 mod tests{
-    use crate::utils::log;
+    use contract_log::contract_log;
     use crate::schedule::Month;
+    use crate::schedule::date::month::MonthNameError;
 
 
     #[test]
@@ -305,8 +274,8 @@ mod tests{
         ///  - expected_day: range 0 to 31, depending on month and if leap year
         /// 
         fn util_day_constructor(days: u64, is_leap_year: bool, expected_month: u8, expected_day: u8) {
-            let (month_str, day) = Month::new_from_days(days, is_leap_year);
-            let month_number = u8::from(&Month::new(&month_str));
+            let (month, day) = Month::new_from_days(days, is_leap_year);
+            let month_number = u8::from(&month);
     
             assert_eq!(
                 month_number, 
@@ -379,12 +348,33 @@ mod tests{
              }
         }
 
-        log("Testing for non leap year.");
+        contract_log!(debug, "Testing for non leap year.");
         testing_year(false);
 
-        log("Testing for leap year.");
+        contract_log!(debug, "Testing for leap year.");
         testing_year(true);
     }
-}
 
 
+    #[test]
+    /// Names and aliases are not case-sensitive, invalid names return an error
+    fn parse_names(){
+        for (index, name) in Month::ALL_VARIANTS.iter().enumerate() {
+            let month: Month = name.parse().unwrap();
+
+            assert_eq!(u8::from(&month), index as u8);
+            assert_eq!(format!("{}", month), *name);
+        }
+
+        let month: Month = "MARÇO".parse().unwrap();
+        assert_eq!(month.get(), "March");
+
+        let month = Month::try_from("Dic").unwrap();
+        assert_eq!(String::from(month), "December");
+
+        assert_eq!(
+            "smarch".parse::<Month>().err(),
+            Some(MonthNameError(String::from("smarch"))),
+        );
+    }
+}
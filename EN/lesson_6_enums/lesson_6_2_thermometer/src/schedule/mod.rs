@@ -22,7 +22,7 @@ use time::Time;
 
 use self::date::month::Month;
 
-use crate::utils::log;
+use contract_log::contract_log;
 
 
 /// Represents a timestamp
@@ -51,7 +51,7 @@ impl Timestamp {
         (hours as u8, minutes as u8, seconds as f32 / 1_000_000_000.)
     }
 
-    fn date_from_nanoseconds(nano: u64) -> (i32, String, u8) {
+    fn date_from_nanoseconds(nano: u64) -> (i32, Month, u8) {
         // add 2 years, account for leap years
         let nano: u64 = nano + 2 * 365 * 24 * 60 * 60 * 1_000_000_000;
 
@@ -76,7 +76,7 @@ impl Timestamp {
     /// date: (year, month, day) tuple.
     /// time: (hour, minute, second) tuple.
     /// 
-    pub fn new(date: Option<(i32, String, u8)>, time: Option<(u8, u8, f32)>) -> Self {
+    pub fn new(date: Option<(i32, Month, u8)>, time: Option<(u8, u8, f32)>) -> Self {
         
         // block_timestamp() = transaction signature time 
         let block_time: u64 = env::block_timestamp();
@@ -84,7 +84,7 @@ impl Timestamp {
         let (year, month, day) = match date{
             Some(value) => value,
             None => {
-                log("Date wasn't specified, using current date.");
+                contract_log!(debug, "Date wasn't specified, using current date.");
                 Self::date_from_nanoseconds(block_time)
             },
         };
@@ -92,16 +92,16 @@ impl Timestamp {
         let (hour, minute, second) = match time{
             Some(value) => value,
             None => {
-                log("Time wasn't specified, using current time");
+                contract_log!(debug, "Time wasn't specified, using current time");
                 Self::time_from_nanoseconds(block_time)
             }
         };
 
-        log(&format!("Epoch time is {}.", block_time));
-        log(&format!("Day: {}, Month: {}, Year: {}", day, &month, year));
-        log(&format!("Hour: {}, Minute: {}, Second: {}", hour, minute, second));
+        contract_log!(trace, "Epoch time is {}.", block_time);
+        contract_log!(trace, "Day: {}, Month: {}, Year: {}", day, &month, year);
+        contract_log!(trace, "Hour: {}, Minute: {}, Second: {}", hour, minute, second);
 
-        let date: Date = Date::new(day, &month, year);
+        let date: Date = Date::new(day, month, year);
         let time: Time = Time::new(hour, minute, second);
 
         Timestamp { 
@@ -15,7 +15,7 @@ use near_sdk::{
 };
 
 use temp_format::TemperatureUnit;
-use crate::utils::log;
+use contract_log::contract_log;
 
 /// A Temperature.
 /// temperature value is f32.
@@ -58,13 +58,13 @@ impl Temperature {
     ///  - Converts to temp_format.
     ///  - Returns temperature.
     /// 
-    pub fn new(temperature_value: f32, temperature_unit: &TemperatureUnit, arg_temp: Option<String>) -> Self {
+    pub fn new(temperature_value: f32, temperature_unit: &TemperatureUnit, arg_temp: Option<TemperatureUnit>) -> Self {
         match arg_temp {
             None => {
                 Temperature::new_assert(temperature_value, temperature_unit.clone())
             },
-            Some(arg_string) => {
-                let mut arg_temp = Temperature::new_assert(temperature_value, TemperatureUnit::new(&arg_string));
+            Some(arg_unit) => {
+                let mut arg_temp = Temperature::new_assert(temperature_value, arg_unit);
 
                 // Convert temperature reading into system temperature unit
                 arg_temp.convert(temperature_unit);
@@ -93,38 +93,38 @@ impl Temperature {
         let current_unit: TemperatureUnit = self.unit.clone();
         let current_value = self.degrees;
         
-        log(&format!("Converting temperature to system format. System Unit: {}, Current Unit: {}.", temperature_unit, &current_unit));
+        contract_log!(debug, "Converting temperature to system format. System Unit: {}, Current Unit: {}.", temperature_unit, &current_unit);
 
         match (current_unit, temperature_unit) {
             (TemperatureUnit::Kelvin(_), &TemperatureUnit::Celsius(_)) => {
                 // C = K − 273.15
                 self.degrees = current_value - 273.15;    
-                self.unit = TemperatureUnit::new("c");
+                self.unit = temperature_unit.clone();
             },
             (TemperatureUnit::Kelvin(_), &TemperatureUnit::Fahrenheit(_)) => {
                 // F = (K – 273.15) × 9⁄5 + 32
                 self.degrees = (current_value - 273.15) * 9.0 / 5.0 + 32.0;
-                self.unit = TemperatureUnit::new("f");
+                self.unit = temperature_unit.clone();
             },
             (TemperatureUnit::Celsius(_), &TemperatureUnit::Kelvin(_)) => {
                 // K = C + 273.15
                 self.degrees = current_value + 273.15;
-                self.unit = TemperatureUnit::new("k");
+                self.unit = temperature_unit.clone();
             },
             (TemperatureUnit::Celsius(_), &TemperatureUnit::Fahrenheit(_)) => {
                 // F = C(9⁄5) + 32
                 self.degrees = current_value * (9.0 / 5.0) + 32.0;
-                self.unit = TemperatureUnit::new("f");
+                self.unit = temperature_unit.clone();
             },
             (TemperatureUnit::Fahrenheit(_), &TemperatureUnit::Kelvin(_)) => {
                 // K = (F − 32) × 5⁄9 + 273.15
                 self.degrees = (current_value - 32.0) * 5.0 / 9.0 + 273.15;
-                self.unit = TemperatureUnit::new("k");
+                self.unit = temperature_unit.clone();
             },
             (TemperatureUnit::Fahrenheit(_), &TemperatureUnit::Celsius(_)) => {
                 // C = (F − 32) × 5⁄9
                 self.degrees = (current_value - 32.0) * 5.0 / 9.0;
-                self.unit = TemperatureUnit::new("c");
+                self.unit = temperature_unit.clone();
             },
             (_, _) => {
                 // all alternatives considered, therefore nothing to do
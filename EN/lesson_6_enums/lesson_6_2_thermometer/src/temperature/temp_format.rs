@@ -3,15 +3,14 @@
 //! Unit can be Kelvin, Celsius or Fahrenheit.
 //! The default is to use Kelvin.
 //! 
-//! Implemented TraitsÖ
+//! Implemented Traits
 //!  - Default. Default unit is Celsius::Kelvin.
 //!  - PartialEq and Eq. Allows comparing between Units.
 //!  - String::from(&temperature_unit) converts a &temperature_unit reference to String;
 //!  - String::from(temperature_unit) converts temperature_unit to String;
-//!  - TemperatureUnit::from("a str") converts an &str to TemperatureUnit;
-//!  - TemperatureUnit::from(aString) converts a String to TemperatureUnit;
-//!  - TemperatureUnit::from(&aString) converts a &String reference to TemperatureUnit;
-//!  - std::fmt::Display. Allows display of value using macros like println!, format! e panic!;
+//!  - NamedVariants (derive). "celsius".parse::<TemperatureUnit>() and TemperatureUnit::try_from("c"),
+//!    returning TemperatureUnitNameError for an invalid name;
+//!  - std::fmt::Display (derive). Allows display of value using macros like println!, format! e panic!;
 //! 
 
 use near_sdk::{
@@ -19,6 +18,8 @@ use near_sdk::{
     serde::{ Deserialize, Serialize },
 };
 
+use named_variants_derive::NamedVariants;
+
 
 /// Represents a TemperatureUnit (Kelvin, Celsius and Fahrenheit).
 /// 
@@ -27,49 +28,31 @@ use near_sdk::{
 /// 
 /// This enum is seen a String when ser/deserialzing JSON.
 /// 
-#[derive(BorshDeserialize, BorshSerialize, Clone, Deserialize, Serialize)]
+/// Not case-sensitive when parsing. You can specify temperature units like:
+/// 
+///  - "celsius", "c" => TempFormat::Celsius("Celsius")
+///  - "fahrenheit", "f" => TempFormat::Fahrenheit("Fahrenheit")
+///  - "kelvin", "k" => TempFormat::Kelvin("Kelvin")
+/// 
+#[derive(BorshDeserialize, BorshSerialize, Clone, Deserialize, NamedVariants, Serialize)]
 #[serde(crate = "near_sdk::serde")]
 #[serde(untagged)]
 pub enum TemperatureUnit {
+    #[alias("c")]
     Celsius(String),
+    #[alias("f")]
     Fahrenheit(String),
+    #[alias("k")]
     Kelvin(String),
 }
 
 
-impl TemperatureUnit {
-    /// Creates a TemperatureUnit
-    /// 
-    /// Not case-sensitive. You can specify temperature units like:
-    /// 
-    ///  - "celsius", "c" => TempFormat::Celsius("Celsius")
-    ///  - "fahrenheit", "f" => TempFormat::Fahrenheit("Fahrenheit")
-    ///  - "kelvin", "k" => TempFormat::Kelvin("Kelvin")
-    /// 
-    /// # Panics
-    /// - If unit name is invalid.
-    /// 
-    pub fn new(unit_name: &str) -> Self{
-        // This conversion for &str to TemperatureUnit is possible due to From<&str> being implemented
-        let lower_case: String = unit_name.to_ascii_lowercase();
-
-        // let's return what matches OR panic!
-        match &lower_case[..] {
-            "celsius" | "c" => TemperatureUnit::Celsius(String::from("Celsius")),
-            "fahrenheit" | "f" => TemperatureUnit::Fahrenheit(String::from("Fahrenheit")),
-            "kelvin" | "k" => TemperatureUnit::Kelvin(String::from("Kelvin")),
-            invalid_name => panic!("Invalid temperature unit name ({}). Valid args: ['Celsius', 'c', 'Fahrenheit', 'f', 'Kelvin', 'k']", invalid_name),
-        }
-    }
-}
-
-
 /// The default unit will set to Kelvin, but 
 /// this can later be changed after contract initialization
 /// 
 impl Default for TemperatureUnit {
     fn default() -> Self {
-        TemperatureUnit::new("k")
+        TemperatureUnit::Kelvin(String::from("Kelvin"))
     }
 }
 
@@ -115,35 +98,3 @@ impl From<TemperatureUnit> for String {
         String::from(&temperature_unit)
     }
 }
-
-
-/// Conversion to TemperatureUnit from &str
-impl From<&str> for TemperatureUnit{
-    fn from(temperature_unit_name: &str) -> TemperatureUnit {
-        TemperatureUnit::new(temperature_unit_name)
-    }
-}
-
-
-/// Conversion to TemperatureUnit from &String
-impl From<&String> for TemperatureUnit{
-    fn from(temperature_unit_name: &String) -> TemperatureUnit {
-        TemperatureUnit::from(&temperature_unit_name[..])
-    }
-}
-
-
-/// Conversion to TemperatureUnit from String
-impl From<String> for TemperatureUnit{
-    fn from(temperature_unit_name: String) -> TemperatureUnit{
-        TemperatureUnit::from(&temperature_unit_name[..])
-    }
-}
-
-
-/// Allos displaying the enum as a String and is used by macros such as format!, println! and panic!.
-impl std::fmt::Display for TemperatureUnit {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", String::from(self))
-    }
-}
@@ -1,30 +1,19 @@
 //! Utilities module
 //! 
-//!  - log: print a message on testing or production environments (different target output)
 //!  - ViewGet: allows having different return typs for the same function.
+//!  - parse_name: parses a month or temperature unit name received by the contract.
 //! 
 
 use near_sdk::serde::{
     Deserialize, Serialize,
 };
 
-use crate::entry::TemperatureReading;
-
-
-#[allow(unused_imports)]
-use near_sdk::env;
-
-/// Prints using println when in a test environment. 
-#[cfg(test)]
-pub fn log(msg: &str){
-    println!("{}", msg);
-}
+use std::{
+    fmt::Display,
+    str::FromStr,
+};
 
-/// Prints using env::log when in a production environment. 
-#[cfg(not(test))]
-pub fn log(msg: &str) {
-    env::log(msg.as_bytes());
-}
+use crate::entry::TemperatureReading;
 
 
 /// Used as return type for view_get function
@@ -45,3 +34,23 @@ pub enum ViewGet{
     Single(TemperatureReading),
     Multiple(Vec<TemperatureReading>),
 }
+
+
+/// Parses a name received by the contract, like a month or a temperature unit.
+/// 
+/// Enums deriving NamedVariants return an error for invalid names.
+/// The contract can't return that error, so it panics with the error message.
+/// 
+/// # Panics
+///  - If name is not valid for T.
+/// 
+pub fn parse_name<T>(name: &str) -> T 
+where 
+    T: FromStr,
+    T::Err: Display,
+{
+    match name.parse() {
+        Ok(value) => value,
+        Err(err) => panic!("{}", err),
+    }
+}
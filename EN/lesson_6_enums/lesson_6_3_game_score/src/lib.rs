@@ -151,7 +151,7 @@ impl Contract{
     pub fn create_character(&mut self, name: String, class: String) -> Result<(), Errors> {
         log!("Create Character function called.");
         
-        let class: Class = Class::new(&class)?;
+        let class: Class = class.parse()?;
         let character: Character = Character::new(name, class)?;
         let mut player: Player = self.load_player()?;
 
@@ -7,8 +7,7 @@ use near_sdk::{
     serde::{ Deserialize, Serialize },
 };
 
-
-use near_sdk::env;
+use named_variants_derive::NamedVariants;
 
 use crate::model::{
     character::Stats,
@@ -16,8 +15,12 @@ use crate::model::{
 };
 
 /// classes: "Warrior" | "Druid" | "Rogue" | "Priest".
-#[derive(BorshDeserialize, BorshSerialize, Clone, Deserialize, Serialize)]
+/// 
+/// NamedVariants parses these names, not case-sensitive.
+/// An invalid name returns Errors::InvalidClassName.
+#[derive(BorshDeserialize, BorshSerialize, Clone, Deserialize, NamedVariants, Serialize)]
 #[serde(crate = "near_sdk::serde")]
+#[named_variants(error = Errors::InvalidClassName)]
 pub enum Class{
     Warrior,
     Druid,
@@ -26,53 +29,13 @@ pub enum Class{
 }
 
 
-impl From<&str> for Class{
-    fn from(class: &str) -> Class{
-        match Class::new(class) {
-            Ok(valid) => valid,
-            Err(err) => env::panic_str(&format!("{}", err)),
-        }
-    }
-}
-
-impl From<String> for Class{
-    fn from(class: String) -> Class {
-        Class::from(&class[..])
-    }
-}
-
-impl From<&String> for Class{
-    fn from(class: &String) -> Class{
-        Class::from(&class[..])
-    }
-}
-
 impl From<&Class> for String {
     fn from(class: &Class) -> String {
-        let name = match *class{
-            Class::Druid => "Druid",
-            Class::Priest => "Priest",
-            Class::Rogue => "Rogue",
-            Class::Warrior => "Warrior",
-        };
-
-        String::from(name)
+        String::from(class.name())
     }
 }
 
 impl Class {
-    pub fn new(class: &str) -> Result<Class, Errors> {
-        let class = match &class.to_ascii_lowercase()[..]{
-            "warrior" => { Class::Warrior },
-            "druid" => { Class::Druid },
-            "rogue" => { Class::Rogue },
-            "priest" => { Class::Priest },
-            invalid => { return Err(Errors::InvalidClassName(String::from(invalid))) },
-        };
-
-        Ok(class)
-    }
-
     /// Return base stats for given class, used by Character;
     pub fn get_stats(&self) -> Stats {
 
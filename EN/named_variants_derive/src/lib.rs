@@ -0,0 +1,365 @@
+//! `#[derive(NamedVariants)]`, a procedural macro that converts enum variants to and from their names.
+//!
+//! Written for lesson_5_macro_usage, and used by the enums of lesson_6_enums.
+//!
+//! ```ignore
+//! #[derive(NamedVariants)]
+//! pub enum TemperatureUnit {
+//!     #[alias("c")]
+//!     Celsius(String),
+//!     #[alias("f")]
+//!     Fahrenheit(String),
+//!     #[alias("k")]
+//!     Kelvin(String),
+//! }
+//! ```
+//!
+//! The name of a variant is its identifier ("Celsius"). The derive generates:
+//!
+//!  - `FromStr`, not case-sensitive, accepting the name and every `#[alias(...)]` of a variant.
+//!  - `TryFrom<&str>`, the same as `FromStr`.
+//!  - `Display`, writing the name.
+//!  - `ALL_VARIANTS`, the names of every variant in declaration order.
+//!  - `name()`, the name of this variant as `&'static str`.
+//!
+//! Variants can be unit variants (`Celsius`), or have a single field built from the name (`Celsius(String)`).
+//!
+//! An invalid name returns an error instead of panicking. By default the error is a new type called
+//! `<Enum>NameError` (`TemperatureUnitNameError`). To use an existing error instead, give a function
+//! or tuple variant that receives the invalid name as a String:
+//!
+//! ```ignore
+//! #[derive(NamedVariants)]
+//! #[named_variants(error = Errors::InvalidClassName)]
+//! pub enum Class { Warrior, Druid, Rogue, Priest }
+//! ```
+//!
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{
+    parse_macro_input,
+    punctuated::Punctuated,
+    Data,
+    DeriveInput,
+    Error,
+    Fields,
+    Ident,
+    LitStr,
+    Path,
+    Token,
+};
+
+
+// A variant, its name and every lowercase string that parses to it
+struct Variant {
+    ident: Ident,
+    name: String,
+    accepted: Vec<String>,
+    // true for Variant(String), false for Variant
+    has_field: bool,
+}
+
+
+#[proc_macro_derive(NamedVariants, attributes(alias, named_variants))]
+pub fn derive_named_variants(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    expand(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let data = match &input.data {
+        Data::Enum(data) => data,
+        _ => return Err(Error::new_spanned(&input.ident, "NamedVariants can only be derived for enums")),
+    };
+
+    if !input.generics.params.is_empty() {
+        return Err(Error::new_spanned(&input.generics, "NamedVariants doesn't support generic enums"));
+    }
+
+    let mut variants: Vec<Variant> = Vec::new();
+    for variant in &data.variants {
+        variants.push(parse_variant(variant)?);
+    }
+
+    check_duplicates(&variants, &input.ident)?;
+
+    let enum_name = &input.ident;
+    let vis = &input.vis;
+
+    // The error type, and how to build it from the invalid name
+    let (error_type, make_error, error_definition) = match parse_error(&input)? {
+        Some(constructor) => {
+            let mut error_type: Path = constructor.clone();
+            error_type.segments.pop();
+            error_type.segments.pop_punct();
+
+            if error_type.segments.is_empty() {
+                return Err(Error::new_spanned(constructor, "expected a path like Errors::InvalidName"));
+            }
+
+            (quote!(#error_type), quote!(#constructor), TokenStream2::new())
+        },
+        None => {
+            let error_name = format_ident!("{}NameError", enum_name);
+            let doc = format!("Returned when a String is not a valid name for {}.", enum_name);
+
+            let definition = quote! {
+                #[doc = #doc]
+                #[derive(Clone, Debug, PartialEq, Eq)]
+                #vis struct #error_name(pub String);
+
+                impl ::std::fmt::Display for #error_name {
+                    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                        write!(
+                            f,
+                            "Invalid name for {} ({}). Valid names: {:?}.",
+                            stringify!(#enum_name),
+                            self.0,
+                            #enum_name::ALL_VARIANTS,
+                        )
+                    }
+                }
+
+                impl ::std::error::Error for #error_name {}
+            };
+
+            (quote!(#error_name), quote!(#error_name), definition)
+        },
+    };
+
+    let names: Vec<&String> = variants.iter().map(|variant| &variant.name).collect();
+
+    let name_arms = variants.iter().map(|variant| {
+        let ident = &variant.ident;
+        let name = &variant.name;
+
+        if variant.has_field {
+            quote!(#enum_name::#ident(..) => #name,)
+        } else {
+            quote!(#enum_name::#ident => #name,)
+        }
+    });
+
+    let parse_arms = variants.iter().map(|variant| {
+        let ident = &variant.ident;
+        let name = &variant.name;
+        let accepted = &variant.accepted;
+
+        if variant.has_field {
+            quote!(#(#accepted)|* => Ok(#enum_name::#ident(::std::convert::From::from(#name))),)
+        } else {
+            quote!(#(#accepted)|* => Ok(#enum_name::#ident),)
+        }
+    });
+
+    Ok(quote! {
+        #error_definition
+
+        impl #enum_name {
+            /// Names of every variant, in the order they were declared.
+            pub const ALL_VARIANTS: &'static [&'static str] = &[#(#names),*];
+
+            /// Name of this variant.
+            pub fn name(&self) -> &'static str {
+                match self {
+                    #(#name_arms)*
+                }
+            }
+        }
+
+        impl ::std::str::FromStr for #enum_name {
+            type Err = #error_type;
+
+            fn from_str(name: &str) -> ::std::result::Result<Self, Self::Err> {
+                match &name.to_lowercase()[..] {
+                    #(#parse_arms)*
+                    _ => Err(#make_error(::std::string::String::from(name))),
+                }
+            }
+        }
+
+        impl ::std::convert::TryFrom<&str> for #enum_name {
+            type Error = #error_type;
+
+            fn try_from(name: &str) -> ::std::result::Result<Self, Self::Error> {
+                name.parse()
+            }
+        }
+
+        impl ::std::fmt::Display for #enum_name {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                write!(f, "{}", self.name())
+            }
+        }
+    })
+}
+
+
+// Reads #[named_variants(error = path)]
+fn parse_error(input: &DeriveInput) -> syn::Result<Option<Path>> {
+    let mut error: Option<Path> = None;
+
+    for attr in input.attrs.iter().filter(|attr| attr.path().is_ident("named_variants")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("error") {
+                error = Some(meta.value()?.parse()?);
+                Ok(())
+            } else {
+                Err(meta.error("expected error"))
+            }
+        })?;
+    }
+
+    Ok(error)
+}
+
+
+fn parse_variant(variant: &syn::Variant) -> syn::Result<Variant> {
+    let has_field = match &variant.fields {
+        Fields::Unit => false,
+        Fields::Unnamed(fields) if fields.unnamed.len() == 1 => true,
+        _ => return Err(Error::new_spanned(variant, "NamedVariants needs unit variants, or variants with a single unnamed field")),
+    };
+
+    let name: String = variant.ident.to_string();
+    let mut accepted: Vec<String> = vec![name.to_lowercase()];
+
+    for attr in variant.attrs.iter().filter(|attr| attr.path().is_ident("alias")) {
+        let aliases = attr.parse_args_with(Punctuated::<LitStr, Token![,]>::parse_terminated)?;
+
+        for alias in aliases {
+            let alias: String = alias.value().to_lowercase();
+
+            if !accepted.contains(&alias) {
+                accepted.push(alias);
+            }
+        }
+    }
+
+    Ok(Variant {
+        ident: variant.ident.clone(),
+        name,
+        accepted,
+        has_field,
+    })
+}
+
+
+// The same alias in two variants would make the second one unreachable
+fn check_duplicates(variants: &[Variant], enum_name: &Ident) -> syn::Result<()> {
+    for (index, variant) in variants.iter().enumerate() {
+        for other in &variants[index + 1..] {
+            if let Some(name) = variant.accepted.iter().find(|name| other.accepted.contains(name)) {
+                return Err(Error::new_spanned(
+                    &other.ident,
+                    format!("\"{}\" is used by both {}::{} and {}::{}", name, enum_name, variant.ident, enum_name, other.ident),
+                ));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::parse_quote;
+
+    fn expand_error(input: DeriveInput) -> String {
+        expand(input).unwrap_err().to_string()
+    }
+
+    #[test]
+    pub fn default_error_type() {
+        let code = expand(parse_quote! {
+            pub enum Class { Warrior, Druid }
+        }).unwrap().to_string().replace(' ', "");
+
+        assert!(code.contains("pubstructClassNameError(pubString);"));
+        assert!(code.contains("typeErr=ClassNameError;"));
+    }
+
+    #[test]
+    pub fn custom_error_type() {
+        let code = expand(parse_quote! {
+            #[named_variants(error = Errors::InvalidClassName)]
+            pub enum Class { Warrior, Druid }
+        }).unwrap().to_string().replace(' ', "");
+
+        assert!(!code.contains("ClassNameError"));
+        assert!(code.contains("typeErr=Errors;"));
+        assert!(code.contains("Err(Errors::InvalidClassName(::std::string::String::from(name)))"));
+    }
+
+    #[test]
+    pub fn errors() {
+        assert_eq!(
+            expand_error(parse_quote! {
+                pub enum Unit {
+                    #[alias("c")]
+                    Celsius,
+                    #[alias("C", "k")]
+                    Kelvin,
+                }
+            }),
+            "\"c\" is used by both Unit::Celsius and Unit::Kelvin",
+        );
+
+        // An alias can't be the name of another variant either
+        assert_eq!(
+            expand_error(parse_quote! {
+                pub enum Unit {
+                    Celsius,
+                    #[alias("CELSIUS")]
+                    Kelvin,
+                }
+            }),
+            "\"celsius\" is used by both Unit::Celsius and Unit::Kelvin",
+        );
+
+        assert_eq!(
+            expand_error(parse_quote! {
+                #[named_variants(error = InvalidName)]
+                pub enum Unit { Celsius }
+            }),
+            "expected a path like Errors::InvalidName",
+        );
+
+        assert_eq!(
+            expand_error(parse_quote! {
+                #[named_variants(message = "invalid")]
+                pub enum Unit { Celsius }
+            }),
+            "expected error",
+        );
+
+        assert_eq!(
+            expand_error(parse_quote! {
+                pub enum Unit { Celsius { degrees: f32 } }
+            }),
+            "NamedVariants needs unit variants, or variants with a single unnamed field",
+        );
+
+        assert_eq!(
+            expand_error(parse_quote! {
+                pub enum Unit<T> { Celsius(T) }
+            }),
+            "NamedVariants doesn't support generic enums",
+        );
+
+        assert_eq!(
+            expand_error(parse_quote! {
+                pub struct Unit;
+            }),
+            "NamedVariants can only be derived for enums",
+        );
+    }
+}
@@ -0,0 +1,77 @@
+use std::convert::TryFrom;
+use std::str::FromStr;
+
+use named_variants_derive::NamedVariants;
+
+
+#[derive(Debug, NamedVariants, PartialEq)]
+pub enum Class {
+    Warrior,
+    #[alias("healer")]
+    Druid,
+    #[alias("thief", "assassin")]
+    Rogue,
+}
+
+#[derive(Debug, NamedVariants, PartialEq)]
+pub enum TemperatureUnit {
+    #[alias("c")]
+    Celsius(String),
+    #[alias("f")]
+    Fahrenheit(String),
+}
+
+
+#[derive(Debug, PartialEq)]
+pub enum Errors {
+    InvalidClassName(String),
+}
+
+#[derive(Debug, NamedVariants, PartialEq)]
+#[named_variants(error = Errors::InvalidClassName)]
+pub enum OtherClass {
+    Priest,
+    Mage,
+}
+
+
+#[test]
+pub fn from_str_is_not_case_sensitive() {
+    assert_eq!(Class::from_str("Warrior"), Ok(Class::Warrior));
+    assert_eq!(Class::from_str("warrior"), Ok(Class::Warrior));
+    assert_eq!(Class::from_str("WARRIOR"), Ok(Class::Warrior));
+    assert_eq!("dRuId".parse::<Class>(), Ok(Class::Druid));
+}
+
+#[test]
+pub fn aliases() {
+    assert_eq!(Class::from_str("Healer"), Ok(Class::Druid));
+    assert_eq!(Class::from_str("thief"), Ok(Class::Rogue));
+    assert_eq!(Class::try_from("ASSASSIN"), Ok(Class::Rogue));
+
+    // Variants with a field get their name
+    assert_eq!(TemperatureUnit::from_str("C"), Ok(TemperatureUnit::Celsius(String::from("Celsius"))));
+    assert_eq!(TemperatureUnit::from_str("fahrenheit"), Ok(TemperatureUnit::Fahrenheit(String::from("Fahrenheit"))));
+}
+
+#[test]
+pub fn names() {
+    assert_eq!(Class::ALL_VARIANTS, &["Warrior", "Druid", "Rogue"]);
+    assert_eq!(Class::Rogue.name(), "Rogue");
+    assert_eq!(Class::Druid.to_string(), "Druid");
+    assert_eq!(TemperatureUnit::Celsius(String::from("anything")).to_string(), "Celsius");
+}
+
+#[test]
+pub fn default_error() {
+    let error: ClassNameError = Class::from_str("Bard").unwrap_err();
+
+    assert_eq!(error, ClassNameError(String::from("Bard")));
+    assert_eq!(error.to_string(), "Invalid name for Class (Bard). Valid names: [\"Warrior\", \"Druid\", \"Rogue\"].");
+}
+
+#[test]
+pub fn custom_error() {
+    assert_eq!(OtherClass::from_str("mage"), Ok(OtherClass::Mage));
+    assert_eq!(OtherClass::from_str("Bard"), Err(Errors::InvalidClassName(String::from("Bard"))));
+}
@@ -1 +0,0 @@
-pub mod a_deep_module;
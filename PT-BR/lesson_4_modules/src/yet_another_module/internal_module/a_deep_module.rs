@@ -1,4 +0,0 @@
-/// Uma função profunda.
-pub fn a_deep_function() -> String {
-    String::from("Called a deep function")
-}
@@ -4,6 +4,7 @@
 //! - panic!
 //! - vec!
 //! - setup_alloc!
+//! - contract_log! (do crate contract_log, compartilhado por todas as lições)
 //! 
 //! 
 //! 
@@ -16,20 +17,9 @@ use near_sdk::{env, near_bindgen};
 near_sdk::setup_alloc!();
 
 
-/// Esta função é usada em ```print_examples```.
-/// É compilada em ambientes de teste.
-#[cfg(test)]
-pub fn log(message: &str) {
-    println!("{}", message);
-}
-
-
-/// Esta função é usada em ```print_examples```.
-/// É compilada fora de ambientes de teste.
-#[cfg(not(test))]
-pub fn log(message: &str) {
-    env::log(message.as_bytes());
-}
+// Imprime com println! em testes, e com env::log na máquina virtual.
+// Mensagens abaixo do nível mínimo (uma feature cargo de contract_log) são removidas na compilação.
+use contract_log::{contract_log, contract_panic};
 
 
 #[near_bindgen]
@@ -45,7 +35,7 @@ impl Contract{
     /// Execute com ```cargo test -- --nocapture```, compile, implante e execute em Near.
     /// Note como algumas mensagens aparecem e outras não.
     pub fn print_examples() {
-        log("\n\nprint_examples:\n");
+        contract_log!(debug, "\n\nprint_examples:\n");
         println!("Isto é um println! Não aparece na máquina virtual");
         let a = String::from("algo");
         println!("Isso é outro println com diversos argumentos, {}, {}, {}", 1, "thingy", a);
@@ -58,92 +48,91 @@ impl Contract{
             .as_bytes()
         );
 
-        log("Isso é uma mensagem que aparecem em testes e na máquina virtual.");
+        contract_log!(info, "Isso é uma mensagem que aparecem em testes e na máquina virtual.");
 
-        log(
-            &format!("Isso é outra mensagem com argumentos, {}, {}, {}.",
-                5,
-                "6",
-                format!("7"),
-            )
+        contract_log!(info, [print_arguments] "Isso é outra mensagem com argumentos, {}, {}, {}.",
+            5,
+            "6",
+            format!("7"),
         );
         
-        log("\n\n---------------------------------------------------------------------------------\n\n");
+        contract_log!(debug, "\n\n---------------------------------------------------------------------------------\n\n");
     }
 
 
     /// Exemplos de format. Compare o output com a implementação.
     pub fn format_examples() {
-        log("\n\nformat_examples:\n");
+        contract_log!(debug, "\n\nformat_examples:\n");
         let message: String = format!("format retorna um String formatado");
-        log(&message);
+        contract_log!(info, "{}", message);
 
         let an_arg ="terceiro";
         let message = format!("format pode receber argumentos usando {{}}: {}, {}, {}.", 1, "second", an_arg);
     
-        log(&message);
+        contract_log!(info, "{}", message);
 
         let (first, second, third) = (1, "segundo", an_arg);
         let message = format!("Podemos especificar argumentos format da seguinte forma: {first}, {second}, {third}.");
 
-        log(&message);
+        contract_log!(info, "{}", message);
 
         let message = format!("Podemos especificar a ordem dos argumentos format: {1}, {2}, {0}.", third, first, second);
 
-        log(&message);
+        contract_log!(info, "{}", message);
 
         let (first, second, third) = (1, 2, 3);
         let message = format!("Podemos fazer inteiros mostrarem um número arbitrário de digitos: {:02}, {:04}, {:6}.", first, second, third);
-        log(&message);
+        contract_log!(info, "{}", message);
 
         let message = format!("Escolhendo um número de digitos e ordem: {2:02}, {0:4}, {1:06}.", second, third, first);
-        log(&message);
+        contract_log!(info, "{}", message);
 
         let (first, second, third) = (0.1, 1.23, -2.45);
         let message = format!("Podemos escolher a precisão de números racionais: {:.2}, {:.4}, {:.6}", first, second, third);
-        log(&message);
+        contract_log!(info, [format_precision] "{}", message);
 
         let message = format!("Podemos escolher a precisão e o número de digitos: {:2.2}, {:04.4}, {:06.6}", first, second, third);
-        log(&message);
+        contract_log!(info, "{}", message);
 
         let message = format!("Podemos escolher a precisão, o número de digitos e a ordem dos argumentos: {1:02.2}, {2:4.4}, {0:06.6}", third, first, second);
-        log(&message);
+        contract_log!(info, "{}", message);
 
         let message = format!("Mesmo que acima: {first:2.2}, {second:04.4}, {third:6.6}");
 
-        log(&message);
+        contract_log!(info, "{}", message);
 
-        log("\n\n----------------------------------------------------------------------\n\n");
+        contract_log!(debug, "\n\n----------------------------------------------------------------------\n\n");
     }
 
     /// Exemplo de panico.
     pub fn panic_example() {
-        log("\n\npanic_example:\n\n\n");
+        contract_log!(debug, "\n\npanic_example:\n\n\n");
 
-        log("Macros de pânico são escritos da mesma forma que println e format.");
+        contract_log!(info, "Macros de pânico são escritos da mesma forma que println e format.");
 
         let second = 2;
-        panic!("Pânico com alguns argumentos: {} {} {}", 1, second, 3);
+        // contract_panic! é um panic! com um ID, para que testes possam verificar em qualquer idioma
+        contract_panic!([panic_example] "Pânico com alguns argumentos: {} {} {}", 1, second, 3);
 
     }
 
     /// Exemplo de usos de vec.
     pub fn vec_examples() {
-        log("\n\nvec_examples:\n");
+        contract_log!(debug, "\n\nvec_examples:\n");
 
         let example = vec![1, 2, 3, 4];
 
-        log(&format!("Podemos imprimir vetores com modo debug:\n{:?}\n\n", example));
+        contract_log!(info, [vec_debug] "Podemos imprimir vetores com modo debug:\n{:?}\n\n", example);
         
-        log(&format!("Podemos imprimir vetores em \"formato legivel\":\n{:#?}\n\n", example));
+        contract_log!(info, "Podemos imprimir vetores em \"formato legivel\":\n{:#?}\n\n", example);
 
-        log(&format!("Podemos fazer o mesmo com tuplas:\n{:#?}\n\n", (1, 2, 3)));
+        contract_log!(info, "Podemos fazer o mesmo com tuplas:\n{:#?}\n\n", (1, 2, 3));
 
-        log(&format!("Podemos criar vetores com valores padrão:\n{:?}\n\n", vec![0;5]));
+        contract_log!(info, "Podemos criar vetores com valores padrão:\n{:?}\n\n", vec![0;5]);
 
-        log("Mais informações na lição de coleções (\"collections\").");
+        contract_log!(info, "Mais informações na lição de coleções (\"collections\").");
 
-        log("\n\n-------------------------------------------------------------------------------\n\n");
+        contract_log!(debug, "\n\n-------------------------------------------------------------------------------\n\n");
     }
 }
 
@@ -154,49 +143,7 @@ impl Contract{
 // --nocapture faz imprimir todo o output, incluindo testes sucedidos.
 // Sem o argumento --test-threads, todos os testes serão async. O que significa que o output será uma bagunça.
 //
+// Os testes são compartilhados com as outras traduções desta lição, por isso verificam IDs de mensagens ao invés do texto.
 #[cfg(test)]
-mod tests{
-    use super::*;
-    use near_sdk::{
-        MockedBlockchain,
-        testing_env,
-        test_utils::VMContextBuilder,
-    };
-
-    fn env_setup(){
-        let builder: VMContextBuilder = VMContextBuilder::new();
-        testing_env!(builder.build());
-
-        // O contrato é stateless. Declaramos, mas não usamos.
-        let _contract: Contract = Contract::default();
-    }
-
-    #[test]
-    pub fn print_examples() {
-        env_setup();
-    
-        Contract::print_examples();
-    }
-
-    #[test]
-    pub fn format_examples() {
-        env_setup();
-    
-        Contract::format_examples();
-    }
-
-    #[test]
-    pub fn vec_examples() {
-        env_setup();
-    
-        Contract::vec_examples();
-    }
-
-    #[test]
-    #[should_panic(expected = "Pânico com alguns argumentos: 1 2 3")]
-    pub fn panic_example() {
-        env_setup();
-
-        Contract::panic_example();
-    }
-}
+#[path = "../../../EN/lesson_5_macro_usage/src/tests.rs"]
+mod tests;